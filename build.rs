@@ -0,0 +1,69 @@
+//! Evaluates whether the debug checks are performed (see `CHECKS_ENABLED`) from the compiler / build settings.
+//!
+//! Emits `miniunchecked_checks_enabled` if they are, which `CHECKS_ENABLED` is derived from,
+//! so that there is a single place resolving the settings. It also gates the tests which violate invariants,
+//! so that they only run when the violations are detected.
+
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTFLAGS");
+    println!("cargo:rerun-if-env-changed=CARGO_ENCODED_RUSTFLAGS");
+    println!("cargo:rustc-check-cfg=cfg(miniunchecked_checks_enabled)");
+
+    if checks_enabled() {
+        println!("cargo:rustc-cfg=miniunchecked_checks_enabled");
+    }
+}
+
+/// Evaluates `CHECKS_ENABLED`, in the order of precedence documented in `src/config.rs`.
+fn checks_enabled() -> bool {
+    let cfg = |name: &str| env::var_os(format!("CARGO_CFG_{name}")).is_some();
+    let feature = |name: &str| env::var_os(format!("CARGO_FEATURE_{name}")).is_some();
+    let checks_cfg = env::var("CARGO_CFG_MINIUNCHECKED_CHECKS").unwrap_or_default();
+    let checks_cfg = |value: &str| checks_cfg.split(',').any(|checks| checks == value);
+
+    // A sanitizer (`-Zsanitizer=...`) may not be detected via a stable `cfg` otherwise.
+    let sanitizer = rustflags().any(|flag| flag.starts_with("sanitizer="));
+
+    // `cfg(ub_checks)` defaults to `debug_assertions`, unless overridden with the (nightly-only) `-Zub-checks` flag.
+    // It is not passed to build scripts, so is evaluated from the flags.
+    let ub_checks = match rustflags()
+        .filter_map(|flag| Some(flag.strip_prefix("ub-checks")?.to_string()))
+        .last()
+    {
+        Some(value) => matches!(value.as_str(), "" | "=yes" | "=y" | "=on" | "=true"),
+        None => cfg("DEBUG_ASSERTIONS"),
+    };
+
+    if checks_cfg("on") {
+        true
+    } else if checks_cfg("off") {
+        false
+    } else if cfg("MIRI") || cfg("FUZZING") || sanitizer {
+        true
+    } else if checks_cfg("ub_checks") {
+        ub_checks
+    } else if feature("NEVER_CHECK") {
+        false
+    } else if feature("ALWAYS_CHECK") {
+        true
+    } else if feature("UB_CHECKS") {
+        ub_checks
+    } else {
+        cfg("DEBUG_ASSERTIONS")
+    }
+}
+
+/// Returns the rustc flags, with the `-Z` prefix (if any) stripped.
+fn rustflags() -> impl Iterator<Item = String> {
+    // `CARGO_ENCODED_RUSTFLAGS` contains the flags separated by `0x1f`,
+    // so both `-Zsanitizer=address` and `-Z sanitizer=address` are split into one or two separate flags.
+    env::var("CARGO_ENCODED_RUSTFLAGS")
+        .unwrap_or_default()
+        .split('\x1f')
+        .map(|flag| flag.trim_start_matches("-Z").to_string())
+        .collect::<Vec<_>>()
+        .into_iter()
+}
@@ -0,0 +1,834 @@
+//! Procedural macros for [`miniunchecked`](https://docs.rs/miniunchecked),
+//! re-exported by it with the `macros` feature.
+
+use proc_macro::{Delimiter, Group, Literal, Spacing, Span, TokenStream, TokenTree};
+
+mod variants;
+
+/// An attribute macro which rewrites the body of a function to use the debug-checked unchecked operations of `miniunchecked`
+/// instead of the checked ones:
+/// - `expr[index]` - slice / string indexing - to `get_unchecked_dbg()` / `get_unchecked_mut_dbg()`
+///   (`SliceExt` / `StrExt`), with the indexing expression as the error message,
+/// - `expr.unwrap()` to `unwrap_unchecked_dbg!(expr)` (`OptionExt` / `ResultDebugExt` / `UncheckedUnwrap`), and
+/// - `unreachable!(...)` to `unreachable_dbg!(...)`.
+///
+/// I.e. the function panics in debug configuration wherever the original code would have panicked,
+/// but doesn't check anything in release configuration.
+///
+/// # Safety
+///
+/// The rewritten operations lead to UB in release configuration wherever the original code would have panicked,
+/// so this must be opted into explicitly - the function must either be an `unsafe fn`,
+/// or its body must be an explicit `unsafe { ... }` block. Otherwise the macro fails to compile.
+///
+/// ```compile_fail
+/// #[miniunchecked::unchecked_dbg]
+/// fn first(values: &[u32]) -> u32 {
+///     values[0]
+/// }
+/// ```
+///
+/// # Limitations
+///
+/// The macro only sees the tokens of the function body, not the types, so
+/// - only slices and strings may be indexed, including via auto-dereferencing (e.g. arrays, `Vec`s and `String`s).
+///   Other types (e.g. maps) fail to compile - use `Index::index(&map, key)` for them.
+/// - an indexing expression is mutable if it is borrowed with `&mut`, or (possibly via fields and further indexing) assigned to.
+///   Calling a method which takes `&mut self` on it (e.g. `v[i].push(x)`) fails to compile - use `(&mut v[i]).push(x)`.
+/// - `.unwrap()` may only be called on [`Option`]s and [`Result`]s.
+/// - closures, `async` blocks and nested items (e.g. functions, `impl`s, `const`s and `static`s) are left as is,
+///   as they may run outside of the function's safety contract (e.g. a closure returned by an `unsafe fn`).
+///   Nested functions may be annotated separately.
+///
+/// The generated code refers to the crate as `::miniunchecked`. If it was renamed, pass its path as `#[unchecked_dbg(crate = path)]`.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::unchecked_dbg;
+///
+/// /// Sums the digits at the start of each row.
+/// ///
+/// /// # Safety
+/// ///
+/// /// `rows` must not be empty, and each row must start with an ASCII digit.
+/// #[unchecked_dbg]
+/// unsafe fn sum_first_digits(rows: &[&str]) -> u32 {
+///     let mut sum = rows[0][..1].parse().unwrap();
+///     for row in &rows[1..] {
+///         sum += match row.as_bytes()[0] {
+///             digit @ b'0'..=b'9' => u32::from(digit - b'0'),
+///             byte => unreachable!("not a digit: {byte}"),
+///         };
+///     }
+///     sum
+/// }
+///
+/// #[unchecked_dbg]
+/// fn swap_rows(grid: &mut [[u8; 3]; 3]) {
+///     // SAFETY: all indices are in bounds.
+///     unsafe {
+///         let first = grid[0];
+///         grid[0] = grid[2];
+///         grid[2] = first;
+///         grid[1][1] += 1;
+///     }
+/// }
+///
+/// assert_eq!(unsafe { sum_first_digits(&["12", "3", "4x"]) }, 8);
+///
+/// let mut grid = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+/// swap_rows(&mut grid);
+/// assert_eq!(grid, [[7, 8, 9], [4, 6, 6], [1, 2, 3]]);
+/// ```
+#[proc_macro_attribute]
+pub fn unchecked_dbg(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match expand_unchecked_dbg(attr, item.clone()) {
+        Ok(item) => item,
+        Err(error) => {
+            // Keep the item, so that its uses don't report errors of their own.
+            let mut tokens = error.into_compile_error();
+            tokens.extend(item);
+            tokens
+        }
+    }
+}
+
+fn expand_unchecked_dbg(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Error> {
+    let krate = crate_path(attr)?;
+
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let not_a_function = Error::new(
+        Span::call_site(),
+        "`#[unchecked_dbg]` may only be applied to functions with a body",
+    );
+    let fn_index = tokens
+        .iter()
+        .position(|token| is_ident(Some(token), "fn"))
+        .ok_or(not_a_function)?;
+    let body = match tokens.last() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => body.clone(),
+        _ => return Err(not_a_function),
+    };
+
+    let is_unsafe_fn = tokens[..fn_index]
+        .iter()
+        .any(|token| is_ident(Some(token), "unsafe"));
+    if !is_unsafe_fn && !is_unsafe_block(body.stream()) {
+        return Err(Error::new(
+            tokens[fn_index].span(),
+            "`#[unchecked_dbg]` requires an `unsafe fn`, or the function body to be an `unsafe { ... }` block, \
+             as the rewritten operations lead to UB in release configuration instead of panicking",
+        ));
+    }
+
+    let rewriter = Rewriter {
+        krate,
+        in_unsafe: false,
+    };
+    let mut rewritten = Group::new(Delimiter::Brace, rewriter.rewrite(body.stream()));
+    rewritten.set_span(body.span());
+    *tokens.last_mut().unwrap() = TokenTree::Group(rewritten);
+
+    Ok(tokens.into_iter().collect())
+}
+
+/// A derive macro which generates debug-checked unchecked accessors for each variant of an enum:
+/// - `as_<variant>_unchecked_dbg(&self)` - returns references to the fields of the variant,
+/// - `as_<variant>_mut_unchecked_dbg(&mut self)` - returns mutable references to them, and
+/// - `into_<variant>_unchecked_dbg(self)` - returns the fields by value,
+///
+/// where `<variant>` is the `snake_case` name of the variant. A single field is returned as is, multiple fields as a tuple
+/// (in declaration order), and no fields as `()`.
+///
+/// The accessors panic in debug configuration if `self` is another variant, with a message naming the enum
+/// and both the expected and the actual variant, but don't check the variant in release configuration
+/// (like [`unreachable_dbg_fmt()`](https://docs.rs/miniunchecked/latest/miniunchecked/fn.unreachable_dbg_fmt.html)),
+/// so they are `unsafe`.
+///
+/// Only enums are supported:
+///
+/// ```compile_fail
+/// #[derive(miniunchecked::UncheckedVariants)]
+/// struct Point {
+///     x: u32,
+/// }
+/// ```
+///
+/// The generated code refers to the crate as `::miniunchecked`. If it was renamed, pass its path as `#[unchecked_variants(crate = path)]`.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::UncheckedVariants;
+///
+/// #[derive(UncheckedVariants)]
+/// enum Token<'a> {
+///     Number(u64),
+///     Ident { name: &'a str, raw: bool },
+///     EndOfFile,
+/// }
+///
+/// let mut token = Token::Ident { name: "x", raw: false };
+///
+/// // SAFETY: `token` is an identifier.
+/// unsafe {
+///     assert_eq!(token.as_ident_unchecked_dbg(), (&"x", &false));
+///     *token.as_ident_mut_unchecked_dbg().1 = true;
+///     assert_eq!(token.into_ident_unchecked_dbg(), ("x", true));
+///
+///     assert_eq!(*Token::Number(7).as_number_unchecked_dbg(), 7);
+///     Token::EndOfFile.into_end_of_file_unchecked_dbg();
+/// }
+/// ```
+#[proc_macro_derive(UncheckedVariants, attributes(unchecked_variants))]
+pub fn unchecked_variants(item: TokenStream) -> TokenStream {
+    variants::expand_unchecked_variants(item).unwrap_or_else(Error::into_compile_error)
+}
+
+/// Parses the (optional) `crate = path` attribute argument.
+fn crate_path(attr: TokenStream) -> Result<TokenStream, Error> {
+    let tokens: Vec<TokenTree> = attr.into_iter().collect();
+    match tokens.as_slice() {
+        [] => Ok("::miniunchecked".parse().unwrap()),
+        [TokenTree::Ident(krate), TokenTree::Punct(eq), path @ ..]
+            if krate.to_string() == "crate" && eq.as_char() == '=' && !path.is_empty() =>
+        {
+            Ok(path.iter().cloned().collect())
+        }
+        [first, ..] => Err(Error::new(first.span(), "expected `crate = path`")),
+    }
+}
+
+/// Whether the function body consists of an `unsafe { ... }` block only.
+fn is_unsafe_block(body: TokenStream) -> bool {
+    let tokens: Vec<TokenTree> = body.into_iter().collect();
+    matches!(
+        tokens.as_slice(),
+        [unsafe_, TokenTree::Group(block)]
+            if is_ident(Some(unsafe_), "unsafe") && block.delimiter() == Delimiter::Brace
+    )
+}
+
+#[derive(Clone, Copy)]
+struct Error {
+    span: Span,
+    message: &'static str,
+}
+
+impl Error {
+    fn new(span: Span, message: &'static str) -> Self {
+        Self { span, message }
+    }
+
+    fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(self.message);
+        message.set_span(self.span);
+        quote(
+            "::core::compile_error!($0);",
+            self.span,
+            &[TokenTree::Literal(message).into()],
+        )
+    }
+}
+
+/// A postfix operation applied to an operand.
+enum Step {
+    /// `[index]`.
+    Index(Group),
+    /// `.unwrap()`, with the span of `unwrap`.
+    Unwrap(Span),
+    /// `.field` / `.0` - keeps a place expression a place.
+    Field(Vec<TokenTree>),
+    /// Anything else - `(args)` of a call, `::<T>` of a method, `?`.
+    Other(Vec<TokenTree>),
+}
+
+struct Rewriter {
+    /// The path to the `miniunchecked` crate.
+    krate: TokenStream,
+    /// Whether the tokens are inside an `unsafe` block, so the unchecked operations don't need one of their own.
+    in_unsafe: bool,
+}
+
+impl Rewriter {
+    fn rewrite(&self, stream: TokenStream) -> TokenStream {
+        let tokens: Vec<TokenTree> = stream.into_iter().collect();
+        let mut out = Vec::with_capacity(tokens.len());
+
+        let mut i = 0;
+        while i < tokens.len() {
+            if is_punct(tokens.get(i), '#') {
+                // Attributes are left as is.
+                let mut end = i + 1;
+                if is_punct(tokens.get(end), '!') {
+                    end += 1;
+                }
+                if matches!(tokens.get(end), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket)
+                {
+                    end += 1;
+                }
+                out.extend_from_slice(&tokens[i..end]);
+                i = end;
+            } else if let (true, Some(TokenTree::Group(block))) =
+                (is_ident(tokens.get(i), "unsafe"), tokens.get(i + 1))
+            {
+                let unsafe_block = Rewriter {
+                    krate: self.krate.clone(),
+                    in_unsafe: true,
+                };
+                out.push(tokens[i].clone());
+                out.push(unsafe_block.rewrite_tree(&TokenTree::Group(block.clone())));
+                i += 2;
+            } else if let Some(end) = closure_end(&tokens, i).or_else(|| item_end(&tokens, i)) {
+                // Not covered by the function's safety contract, so the checked operations are kept.
+                out.extend_from_slice(&tokens[i..end]);
+                i = end;
+            } else if starts_operand(&tokens, i) {
+                i = self.rewrite_operand(&tokens, i, &mut out);
+            } else {
+                out.push(self.rewrite_tree(&tokens[i]));
+                i += 1;
+            }
+        }
+
+        out.into_iter().collect()
+    }
+
+    fn rewrite_tree(&self, tree: &TokenTree) -> TokenTree {
+        match tree {
+            TokenTree::Group(group) => {
+                let mut rewritten = Group::new(group.delimiter(), self.rewrite(group.stream()));
+                rewritten.set_span(group.span());
+                TokenTree::Group(rewritten)
+            }
+            tree => tree.clone(),
+        }
+    }
+
+    /// Rewrites the operand (a path, literal, parenthesized expression or macro call) starting at `tokens[start]`
+    /// together with all postfix operations applied to it, and returns the index of the token following them.
+    fn rewrite_operand(
+        &self,
+        tokens: &[TokenTree],
+        start: usize,
+        out: &mut Vec<TokenTree>,
+    ) -> usize {
+        let operand_end = operand_end(tokens, start);
+        let mut expr = self.rewrite_primary(&tokens[start..operand_end]);
+
+        let mut steps = Vec::new();
+        let mut end = operand_end;
+        while let Some((step, step_end)) = step(tokens, end) {
+            steps.push((step, step_end));
+            end = step_end;
+        }
+
+        // The indexing steps which are only followed by fields / further indexing
+        // are mutable if the whole expression is borrowed mutably or assigned to.
+        let mutable = is_mut_borrowed(out) || is_assignment(tokens, end);
+        let place_steps = steps
+            .iter()
+            .rposition(|(step, _)| !matches!(step, Step::Index(_) | Step::Field(_)))
+            .map_or(0, |position| position + 1);
+
+        for (position, (step, step_end)) in steps.into_iter().enumerate() {
+            expr = match step {
+                Step::Index(index) => {
+                    let description = format!("in `{}`", source_text(&tokens[start..step_end]));
+                    self.index(
+                        expr,
+                        &index,
+                        mutable && position >= place_steps,
+                        &description,
+                    )
+                }
+                Step::Unwrap(span) => self.unwrap(expr, span),
+                Step::Field(tokens) | Step::Other(tokens) => {
+                    expr.extend(tokens.iter().map(|token| self.rewrite_tree(token)));
+                    expr
+                }
+            };
+        }
+
+        out.extend(expr);
+        end
+    }
+
+    fn rewrite_primary(&self, tokens: &[TokenTree]) -> TokenStream {
+        if let [.., TokenTree::Ident(name), bang, TokenTree::Group(args)] = tokens
+            && name.to_string() == "unreachable"
+            && is_punct(Some(bang), '!')
+        {
+            return self.unreachable(args, name.span());
+        }
+        tokens
+            .iter()
+            .map(|token| self.rewrite_tree(token))
+            .collect()
+    }
+
+    fn index(
+        &self,
+        indexed: TokenStream,
+        index: &Group,
+        mutable: bool,
+        description: &str,
+    ) -> TokenStream {
+        let template = if mutable {
+            "(*match (
+                {
+                    #[allow(unused_imports)]
+                    use $2::__private::AsMutIndexed as _;
+                    ($0).__miniunchecked_as_mut()
+                },
+                $1,
+            ) {
+                (__miniunchecked_indexed, __miniunchecked_index) => {
+                    #[allow(unused_imports)]
+                    use $2::{SliceExt as _, StrExt as _};
+                    $4 { __miniunchecked_indexed.get_unchecked_mut_dbg_msg(__miniunchecked_index, $3) }
+                }
+            })"
+        } else {
+            "(*match (&($0), $1) {
+                (__miniunchecked_indexed, __miniunchecked_index) => {
+                    #[allow(unused_imports)]
+                    use $2::{SliceExt as _, StrExt as _};
+                    $4 { __miniunchecked_indexed.get_unchecked_dbg_msg(__miniunchecked_index, $3) }
+                }
+            })"
+        };
+        let span = index.span();
+        let mut description = Literal::string(description);
+        description.set_span(span);
+        quote(
+            template,
+            span,
+            &[
+                indexed,
+                self.rewrite(index.stream()),
+                self.krate(span),
+                TokenTree::Literal(description).into(),
+                self.unsafe_keyword(span),
+            ],
+        )
+    }
+
+    fn unwrap(&self, expr: TokenStream, span: Span) -> TokenStream {
+        quote(
+            "(match $0 {
+                __miniunchecked_value => $2 {
+                    #[allow(unused_imports)]
+                    use $1::__private::{DebugResultKind as _, UncheckedUnwrapKind as _};
+                    (&$1::__private::Unwrap(&__miniunchecked_value))
+                        .__miniunchecked_unwrap_kind()
+                        .unwrap(__miniunchecked_value)
+                }
+            })",
+            span,
+            &[expr, self.krate(span), self.unsafe_keyword(span)],
+        )
+    }
+
+    fn unreachable(&self, args: &Group, span: Span) -> TokenStream {
+        if args.stream().is_empty() {
+            quote(
+                "$1 { $0::unreachable_dbg() }",
+                span,
+                &[self.krate(span), self.unsafe_keyword(span)],
+            )
+        } else {
+            quote(
+                "match ::core::format_args!($2) {
+                    __miniunchecked_args => $1 { $0::unreachable_dbg_fmt(__miniunchecked_args) }
+                }",
+                span,
+                &[
+                    self.krate(span),
+                    self.unsafe_keyword(span),
+                    self.rewrite(args.stream()),
+                ],
+            )
+        }
+    }
+
+    /// The path to the `miniunchecked` crate, located at `span`.
+    ///
+    /// `#[track_caller]` reports the location of a call by the span of the callee path, if any.
+    fn krate(&self, span: Span) -> TokenStream {
+        respan(self.krate.clone(), span)
+    }
+
+    /// `unsafe` if the unchecked operations need an `unsafe` block, nothing otherwise
+    /// (avoiding "unnecessary `unsafe` block" warnings).
+    fn unsafe_keyword(&self, span: Span) -> TokenStream {
+        if self.in_unsafe {
+            TokenStream::new()
+        } else {
+            quote("unsafe", span, &[])
+        }
+    }
+}
+
+/// Whether `tokens[i]` starts an operand which postfix operations may be applied to.
+fn starts_operand(tokens: &[TokenTree], i: usize) -> bool {
+    if let Some(TokenTree::Punct(prev)) = i.checked_sub(1).map(|prev| &tokens[prev]) {
+        match prev.as_char() {
+            // Lifetimes / labels, and `macro_rules!` metavariables.
+            '\'' | '$' => return false,
+            // A field / method of an expression which is not rewritten (e.g. a block), unless it's a range.
+            '.' if !matches!(
+                i.checked_sub(2).map(|prev| &tokens[prev]),
+                Some(TokenTree::Punct(dot)) if dot.as_char() == '.' && dot.spacing() == Spacing::Joint
+            ) =>
+            {
+                return false;
+            }
+            _ => {}
+        }
+    }
+
+    match &tokens[i] {
+        TokenTree::Literal(_) => true,
+        TokenTree::Group(group) => group.delimiter() != Delimiter::Brace,
+        TokenTree::Ident(ident) => !KEYWORDS.contains(&ident.to_string().as_str()),
+        TokenTree::Punct(_) => is_path_sep(tokens, i),
+    }
+}
+
+/// Returns the index of the token following the closure or `async` block starting at `tokens[i]`, if any.
+fn closure_end(tokens: &[TokenTree], i: usize) -> Option<usize> {
+    let mut params = i;
+    if is_ident(tokens.get(params), "async") {
+        params += 1;
+    }
+    if is_ident(tokens.get(params), "move") {
+        params += 1;
+    }
+    match tokens.get(params) {
+        Some(TokenTree::Group(block)) if params > i && block.delimiter() == Delimiter::Brace => {
+            return Some(params + 1);
+        }
+        Some(TokenTree::Punct(pipe)) if pipe.as_char() == '|' => {}
+        _ => return None,
+    }
+    // Not the `|` / `||` operator.
+    if params == i && !starts_expression(tokens, i) {
+        return None;
+    }
+
+    // `||` without parameters, or `|params|`.
+    let body = if matches!(&tokens[params], TokenTree::Punct(pipe) if pipe.spacing() == Spacing::Joint)
+        && is_punct(tokens.get(params + 1), '|')
+    {
+        params + 2
+    } else {
+        let params_end = tokens[params + 1..]
+            .iter()
+            .position(|token| is_punct(Some(token), '|'))?;
+        params + params_end + 2
+    };
+
+    if is_punct(tokens.get(body), '-') && is_punct(tokens.get(body + 1), '>') {
+        // The body is a block following the return type.
+        let block = tokens[body..].iter().position(
+            |token| matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace),
+        )?;
+        return Some(body + block + 1);
+    }
+
+    // The body is an expression, which ends at the end of the enclosing one.
+    let mut end = body;
+    while let Some(token) = tokens.get(end) {
+        if is_punct(Some(token), ',') || is_punct(Some(token), ';') {
+            break;
+        }
+        end = if is_path_sep(tokens, end) && is_punct(tokens.get(end + 2), '<') {
+            angle_brackets_end(tokens, end + 2)
+        } else {
+            end + 1
+        };
+    }
+    Some(end)
+}
+
+/// Whether `tokens[i]` is at the start of an expression (as opposed to following an operand, like a binary operator).
+fn starts_expression(tokens: &[TokenTree], i: usize) -> bool {
+    match i.checked_sub(1).map(|prev| &tokens[prev]) {
+        None => true,
+        Some(TokenTree::Punct(prev)) => prev.as_char() != '?',
+        Some(TokenTree::Ident(prev)) => {
+            matches!(
+                prev.to_string().as_str(),
+                "return" | "break" | "yield" | "in"
+            )
+        }
+        Some(TokenTree::Literal(_) | TokenTree::Group(_)) => false,
+    }
+}
+
+/// Returns the index of the token following the nested item starting at `tokens[i]`, if any.
+fn item_end(tokens: &[TokenTree], i: usize) -> Option<usize> {
+    let TokenTree::Ident(keyword) = &tokens[i] else {
+        return None;
+    };
+    // Not a lifetime (`'static`), a raw pointer type (`*const T`) or a raw borrow (`&raw const x`).
+    if let Some(prev) = i.checked_sub(1).map(|prev| &tokens[prev])
+        && (is_punct(Some(prev), '\'') || is_punct(Some(prev), '*') || is_ident(Some(prev), "raw"))
+    {
+        return None;
+    }
+
+    let until = |stop_at_block: bool| {
+        let end = tokens[i..].iter().position(|token| match token {
+            TokenTree::Group(group) => stop_at_block && group.delimiter() == Delimiter::Brace,
+            token => is_punct(Some(token), ';'),
+        });
+        Some(end.map_or(tokens.len(), |end| i + end + 1))
+    };
+
+    match keyword.to_string().as_str() {
+        // Followed by the name, unlike e.g. a function pointer type or a variable named `union`.
+        "fn" | "mod" | "trait" | "struct" | "enum" | "union"
+            if matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))) =>
+        {
+            until(true)
+        }
+        "impl" => until(true),
+        "const" | "static" => match tokens.get(i + 1) {
+            // An inline `const` block.
+            Some(TokenTree::Group(block)) if block.delimiter() == Delimiter::Brace => Some(i + 2),
+            // A `const fn`, handled as a function.
+            Some(next)
+                if ["fn", "unsafe", "async", "extern"]
+                    .iter()
+                    .any(|name| is_ident(Some(next), name)) =>
+            {
+                None
+            }
+            _ => until(false),
+        },
+        _ => None,
+    }
+}
+
+/// The keywords which may not start an operand (unlike e.g. `self` or `true`).
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+    "while", "yield",
+];
+
+/// Returns the index of the token following the operand starting at `tokens[start]`.
+fn operand_end(tokens: &[TokenTree], start: usize) -> usize {
+    if !matches!(tokens[start], TokenTree::Ident(_)) && !is_path_sep(tokens, start) {
+        return start + 1;
+    }
+
+    // A path, possibly with turbofish generic arguments.
+    let mut end = start;
+    loop {
+        if is_path_sep(tokens, end) {
+            end += 2;
+        }
+        match tokens.get(end) {
+            Some(TokenTree::Ident(_)) => end += 1,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '<' && end > start => {
+                end = angle_brackets_end(tokens, end)
+            }
+            _ => break,
+        }
+        if !is_path_sep(tokens, end) {
+            break;
+        }
+    }
+
+    // A macro call.
+    if is_punct(tokens.get(end), '!') && matches!(tokens.get(end + 1), Some(TokenTree::Group(_))) {
+        end += 2;
+    }
+
+    end
+}
+
+/// Parses the postfix operation starting at `tokens[i]`, if any, and returns it with the index of the token following it.
+fn step(tokens: &[TokenTree], i: usize) -> Option<(Step, usize)> {
+    match tokens.get(i)? {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => {
+            Some((Step::Index(group.clone()), i + 1))
+        }
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+            Some((Step::Other(vec![tokens[i].clone()]), i + 1))
+        }
+        TokenTree::Punct(punct) if punct.as_char() == '?' => {
+            Some((Step::Other(vec![tokens[i].clone()]), i + 1))
+        }
+        // Not a range.
+        TokenTree::Punct(punct) if punct.as_char() == '.' && punct.spacing() == Spacing::Alone => {
+            match tokens.get(i + 1)? {
+                TokenTree::Ident(name)
+                    if name.to_string() == "unwrap"
+                        && matches!(
+                            tokens.get(i + 2),
+                            Some(TokenTree::Group(args))
+                                if args.delimiter() == Delimiter::Parenthesis && args.stream().is_empty()
+                        ) =>
+                {
+                    Some((Step::Unwrap(name.span()), i + 3))
+                }
+                TokenTree::Ident(_)
+                    if is_path_sep(tokens, i + 2) && is_punct(tokens.get(i + 4), '<') =>
+                {
+                    let end = angle_brackets_end(tokens, i + 4);
+                    Some((Step::Other(tokens[i..end].to_vec()), end))
+                }
+                TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                    Some((Step::Field(tokens[i..i + 2].to_vec()), i + 2))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the index of the token following the `>` matching the `<` at `tokens[start]`.
+fn angle_brackets_end(tokens: &[TokenTree], start: usize) -> usize {
+    let mut depth = 0_usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        let TokenTree::Punct(punct) = token else {
+            continue;
+        };
+        match punct.as_char() {
+            '<' => depth += 1,
+            // Not `->`.
+            '>' if !matches!(&tokens[i - 1], TokenTree::Punct(prev) if prev.as_char() == '-' && prev.spacing() == Spacing::Joint) =>
+            {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Whether the tokens rewritten so far end with `&mut` / `&raw mut`.
+fn is_mut_borrowed(out: &[TokenTree]) -> bool {
+    match out {
+        [.., amp, mut_] if is_punct(Some(amp), '&') => is_ident(Some(mut_), "mut"),
+        [.., amp, raw, mut_] if is_punct(Some(amp), '&') => {
+            is_ident(Some(raw), "raw") && is_ident(Some(mut_), "mut")
+        }
+        _ => false,
+    }
+}
+
+/// Whether `tokens[i]` starts an assignment or a compound assignment operator.
+fn is_assignment(tokens: &[TokenTree], i: usize) -> bool {
+    let Some(TokenTree::Punct(punct)) = tokens.get(i) else {
+        return false;
+    };
+    let joint = punct.spacing() == Spacing::Joint;
+    match punct.as_char() {
+        // Not `==` / `=>`.
+        '=' => !joint || !(is_punct(tokens.get(i + 1), '=') || is_punct(tokens.get(i + 1), '>')),
+        '+' | '-' | '*' | '/' | '%' | '^' | '&' | '|' => joint && is_punct(tokens.get(i + 1), '='),
+        // `<<=` / `>>=`.
+        c @ ('<' | '>') => {
+            joint
+                && matches!(tokens.get(i + 1), Some(TokenTree::Punct(next)) if next.as_char() == c && next.spacing() == Spacing::Joint)
+                && is_punct(tokens.get(i + 2), '=')
+        }
+        _ => false,
+    }
+}
+
+fn is_path_sep(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(tokens.get(i), Some(TokenTree::Punct(colon)) if colon.as_char() == ':' && colon.spacing() == Spacing::Joint)
+        && is_punct(tokens.get(i + 1), ':')
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+fn is_ident(token: Option<&TokenTree>, name: &str) -> bool {
+    matches!(token, Some(TokenTree::Ident(ident)) if ident.to_string() == name)
+}
+
+/// Returns the source code of `tokens` as written, or as printed by the compiler if it's not available.
+fn source_text(tokens: &[TokenTree]) -> String {
+    let mut text = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let span = token.span();
+        if let Some(prev) = i.checked_sub(1).map(|prev| tokens[prev].span().end()) {
+            let start = span.start();
+            if (prev.line(), prev.column()) != (start.line(), start.column()) {
+                text.push(' ');
+            }
+        }
+        match span.source_text() {
+            Some(source) => text.push_str(&source),
+            None => text.push_str(&token.to_string()),
+        }
+    }
+    text
+}
+
+/// Sets the span of all `tokens` to `span`.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::Group(respanned)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
+}
+
+/// Parses the `template`, replacing each `$n` with `args[n]`, and sets the span of its tokens to `span`.
+///
+/// NOTE: the span is not resolved at the mixed site, as then `#[track_caller]` would report the location
+/// of the attribute instead of the one of the rewritten expression. Hence the `__miniunchecked` prefix of local variables.
+fn quote(template: &str, span: Span, args: &[TokenStream]) -> TokenStream {
+    fn substitute(stream: TokenStream, span: Span, args: &[TokenStream]) -> TokenStream {
+        let mut out = TokenStream::new();
+        let mut tokens = stream.into_iter();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                    let Some(TokenTree::Literal(arg)) = tokens.next() else {
+                        unreachable!("`$` not followed by an argument index in a template");
+                    };
+                    out.extend(args[arg.to_string().parse::<usize>().unwrap()].clone());
+                }
+                TokenTree::Group(group) => {
+                    let mut substituted =
+                        Group::new(group.delimiter(), substitute(group.stream(), span, args));
+                    substituted.set_span(span);
+                    out.extend([TokenTree::Group(substituted)]);
+                }
+                mut token => {
+                    token.set_span(span);
+                    out.extend([token]);
+                }
+            }
+        }
+        out
+    }
+
+    substitute(template.parse().expect("invalid template"), span, args)
+}
@@ -0,0 +1,373 @@
+//! The implementation of `#[derive(UncheckedVariants)]`.
+
+use {
+    crate::{Error, angle_brackets_end, crate_path, is_ident, is_punct, quote},
+    proc_macro::{Delimiter, Group, Ident, Literal, Spacing, Span, TokenStream, TokenTree},
+};
+
+/// The fields of a variant.
+enum Fields {
+    Unit,
+    /// The types of the fields.
+    Tuple(Vec<TokenStream>),
+    /// The names and types of the fields.
+    Named(Vec<(Ident, TokenStream)>),
+}
+
+struct Variant {
+    name: Ident,
+    fields: Fields,
+}
+
+pub(crate) fn expand_unchecked_variants(item: TokenStream) -> Result<TokenStream, Error> {
+    let tokens: Vec<TokenTree> = item.into_iter().collect();
+
+    // Outer attributes, including the (optional) `#[unchecked_variants(crate = path)]`.
+    let mut krate = None;
+    let mut i = 0;
+    while let (true, Some(TokenTree::Group(attr))) =
+        (is_punct(tokens.get(i), '#'), tokens.get(i + 1))
+    {
+        let attr: Vec<TokenTree> = attr.stream().into_iter().collect();
+        if let [name, TokenTree::Group(args)] = attr.as_slice()
+            && is_ident(Some(name), "unchecked_variants")
+        {
+            krate = Some(crate_path(args.stream())?);
+        }
+        i += 2;
+    }
+    let krate = match krate {
+        Some(krate) => krate,
+        None => crate_path(TokenStream::new())?,
+    };
+
+    let vis_start = i;
+    if is_ident(tokens.get(i), "pub") {
+        i += 1;
+        if matches!(tokens.get(i), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+        {
+            i += 1;
+        }
+    }
+    let vis: TokenStream = tokens[vis_start..i].iter().cloned().collect();
+
+    if !is_ident(tokens.get(i), "enum") {
+        return Err(Error::new(
+            tokens.get(i).map_or_else(Span::call_site, TokenTree::span),
+            "`#[derive(UncheckedVariants)]` may only be applied to enums",
+        ));
+    }
+    let Some(TokenTree::Ident(name)) = tokens.get(i + 1) else {
+        unreachable!("an enum without a name");
+    };
+    i += 2;
+
+    let (impl_generics, type_generics) = if is_punct(tokens.get(i), '<') {
+        let end = angle_brackets_end(&tokens, i);
+        let generics = generics(&tokens[i + 1..end - 1]);
+        i = end;
+        generics
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
+    let Some(body) = tokens.iter().rposition(
+        |token| matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace),
+    ) else {
+        unreachable!("an enum without a body");
+    };
+    let where_clause: TokenStream = tokens[i..body].iter().cloned().collect();
+    let TokenTree::Group(body) = &tokens[body] else {
+        unreachable!();
+    };
+    let variants = variants(body.stream());
+
+    let mut methods = TokenStream::new();
+    for variant in &variants {
+        methods.extend(accessors(&krate, &vis, name, variant, &variants));
+    }
+
+    Ok(quote(
+        "impl<$0> $1<$2> $3 { $4 }",
+        Span::call_site(),
+        &[
+            impl_generics,
+            TokenTree::Ident(name.clone()).into(),
+            type_generics,
+            where_clause,
+            methods,
+        ],
+    ))
+}
+
+/// Splits the generic parameters into the ones of the `impl` (without defaults) and the arguments of the type.
+fn generics(params: &[TokenTree]) -> (TokenStream, TokenStream) {
+    let mut impl_generics = TokenStream::new();
+    let mut type_generics = TokenStream::new();
+    for param in split(params) {
+        let param = skip_attributes(&param);
+        let name = match param {
+            // A lifetime.
+            [TokenTree::Punct(apostrophe), _, ..] if apostrophe.as_char() == '\'' => &param[..2],
+            [const_, name, ..] if is_ident(Some(const_), "const") => core::slice::from_ref(name),
+            [name, ..] => core::slice::from_ref(name),
+            [] => continue,
+        };
+        type_generics.extend(name.iter().cloned());
+        type_generics.extend(comma());
+
+        // Without the default, if any.
+        let default = split_at(param, '=').swap_remove(0);
+        impl_generics.extend(default);
+        impl_generics.extend(comma());
+    }
+    (impl_generics, type_generics)
+}
+
+fn variants(body: TokenStream) -> Vec<Variant> {
+    let tokens: Vec<TokenTree> = body.into_iter().collect();
+    tokens
+        .split(|token| is_punct(Some(token), ','))
+        .filter_map(|variant| match skip_attributes(variant) {
+            [TokenTree::Ident(name), rest @ ..] => Some(Variant {
+                name: name.clone(),
+                fields: match rest.first() {
+                    Some(TokenTree::Group(fields))
+                        if fields.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        Fields::Tuple(fields_of(fields).map(|(_, ty)| ty).collect())
+                    }
+                    Some(TokenTree::Group(fields)) if fields.delimiter() == Delimiter::Brace => {
+                        Fields::Named(
+                            fields_of(fields)
+                                .map(|(name, ty)| (name.expect("a named field without a name"), ty))
+                                .collect(),
+                        )
+                    }
+                    _ => Fields::Unit,
+                },
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the (optional) names and the types of the fields declared in the `fields` group.
+fn fields_of(fields: &Group) -> impl Iterator<Item = (Option<Ident>, TokenStream)> {
+    let tokens: Vec<TokenTree> = fields.stream().into_iter().collect();
+    let named = fields.delimiter() == Delimiter::Brace;
+    split(&tokens).into_iter().filter_map(move |field| {
+        let mut field = skip_attributes(&field);
+        if is_ident(field.first(), "pub") {
+            field = &field[1..];
+            if matches!(field.first(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+            {
+                field = &field[1..];
+            }
+        }
+        match field {
+            [] => None,
+            [TokenTree::Ident(name), _colon, ty @ ..] if named => {
+                Some((Some(name.clone()), ty.iter().cloned().collect()))
+            }
+            ty => Some((None, ty.iter().cloned().collect())),
+        }
+    })
+}
+
+/// Generates the `as_*_unchecked_dbg()`, `as_*_mut_unchecked_dbg()` and `into_*_unchecked_dbg()` methods of the `variant`.
+fn accessors(
+    krate: &TokenStream,
+    vis: &TokenStream,
+    enum_name: &Ident,
+    variant: &Variant,
+    variants: &[Variant],
+) -> TokenStream {
+    let span = Span::call_site();
+    let variant_name = &variant.name;
+    let snake_name = snake_case(&variant_name.to_string());
+
+    let (pattern, bindings, types) = match &variant.fields {
+        Fields::Unit => (TokenStream::new(), Vec::new(), Vec::new()),
+        Fields::Tuple(types) => {
+            let bindings: Vec<TokenStream> = (0..types.len())
+                .map(|i| TokenTree::Ident(Ident::new(&format!("__miniunchecked_{i}"), span)).into())
+                .collect();
+            let pattern = group(Delimiter::Parenthesis, separated(bindings.iter().cloned()));
+            (pattern, bindings, types.clone())
+        }
+        Fields::Named(fields) => {
+            let bindings: Vec<TokenStream> = fields
+                .iter()
+                .map(|(name, _)| TokenTree::Ident(name.clone()).into())
+                .collect();
+            let pattern = group(Delimiter::Brace, separated(bindings.iter().cloned()));
+            let types = fields.iter().map(|(_, ty)| ty.clone()).collect();
+            (pattern, bindings, types)
+        }
+    };
+    let value = tuple(bindings);
+
+    // Maps the actual variant to its name.
+    let mut names = TokenStream::new();
+    for other in variants {
+        names.extend(quote(
+            "Self::$0 { .. } => $1,",
+            span,
+            &[
+                TokenTree::Ident(other.name.clone()).into(),
+                TokenTree::Literal(Literal::string(&other.name.to_string())).into(),
+            ],
+        ));
+    }
+
+    let mut methods = TokenStream::new();
+    for (receiver, prefix, suffix, reference) in [
+        ("&self", "as", "", "&"),
+        ("&mut self", "as", "_mut", "&mut"),
+        ("self", "into", "", ""),
+    ] {
+        let method = format!("{prefix}_{snake_name}{suffix}_unchecked_dbg");
+        let output = tuple(types.iter().map(|ty| {
+            let mut output: TokenStream = reference.parse().unwrap();
+            output.extend(ty.clone());
+            output
+        }));
+        let doc = match (reference, &variant.fields) {
+            (_, Fields::Unit) => {
+                format!(" Asserts that `self` is the [`{enum_name}::{variant_name}`] variant,")
+            }
+            ("", _) => {
+                format!(" Returns the fields of the [`{enum_name}::{variant_name}`] variant,")
+            }
+            _ => format!(
+                " Returns references to the fields of the [`{enum_name}::{variant_name}`] variant,"
+            ),
+        };
+        let message = format!("called `{enum_name}::{method}()` on a `{enum_name}::{{}}` value");
+
+        methods.extend(quote(
+            "#[doc = $0]
+            /// panicking in debug configuration if `self` is another variant,
+            /// but not checking the variant in release configuration.
+            ///
+            /// # Safety
+            ///
+            /// `self` must be the variant, see [`unreachable_unchecked()`](::core::hint::unreachable_unchecked) documentation.
+            #[inline]
+            #[track_caller]
+            $1 unsafe fn $2($3) -> $4 {
+                match self {
+                    Self::$5 $6 => $7,
+                    #[allow(unreachable_patterns)]
+                    ref __miniunchecked_other => unsafe {
+                        $8::unreachable_dbg_fmt(::core::format_args!(
+                            $9,
+                            match __miniunchecked_other { $10 },
+                        ))
+                    },
+                }
+            }",
+            span,
+            &[
+                TokenTree::Literal(Literal::string(&doc)).into(),
+                vis.clone(),
+                TokenTree::Ident(Ident::new(&method, span)).into(),
+                receiver.parse().unwrap(),
+                output,
+                TokenTree::Ident(variant_name.clone()).into(),
+                pattern.clone(),
+                value.clone(),
+                krate.clone(),
+                TokenTree::Literal(Literal::string(&message)).into(),
+                names.clone(),
+            ],
+        ));
+    }
+    methods
+}
+
+/// Converts a `CamelCase` variant name to `snake_case`, e.g. `HttpError` / `HTTPError` to `http_error`.
+fn snake_case(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev != '_' && (!prev.is_uppercase() || next_is_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Splits `tokens` at the commas which are not inside angle brackets.
+fn split(tokens: &[TokenTree]) -> Vec<Vec<TokenTree>> {
+    split_at(tokens, ',')
+}
+
+/// Splits `tokens` at the `separator`s which are not inside angle brackets.
+fn split_at(tokens: &[TokenTree], separator: char) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0_usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(punct) = token {
+            match punct.as_char() {
+                c if c == separator && depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                '<' => depth += 1,
+                // Not `->`.
+                '>' if !matches!(i.checked_sub(1).map(|prev| &tokens[prev]), Some(TokenTree::Punct(prev)) if prev.as_char() == '-' && prev.spacing() == Spacing::Joint) => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+        }
+        parts.last_mut().unwrap().push(token.clone());
+    }
+    parts
+}
+
+/// Returns `tokens` without the leading attributes.
+fn skip_attributes(mut tokens: &[TokenTree]) -> &[TokenTree] {
+    while let [hash, TokenTree::Group(attr), rest @ ..] = tokens
+        && is_punct(Some(hash), '#')
+        && attr.delimiter() == Delimiter::Bracket
+    {
+        tokens = rest;
+    }
+    tokens
+}
+
+/// Returns the single item itself, or a tuple of the items.
+fn tuple(items: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+    let mut items: Vec<TokenStream> = items.into_iter().collect();
+    if items.len() == 1 {
+        items.pop().unwrap()
+    } else {
+        group(Delimiter::Parenthesis, separated(items))
+    }
+}
+
+fn separated(items: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+    let mut out = TokenStream::new();
+    for item in items {
+        out.extend(item);
+        out.extend(comma());
+    }
+    out
+}
+
+fn group(delimiter: Delimiter, stream: TokenStream) -> TokenStream {
+    TokenTree::Group(Group::new(delimiter, stream)).into()
+}
+
+fn comma() -> TokenStream {
+    ",".parse().unwrap()
+}
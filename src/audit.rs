@@ -0,0 +1,154 @@
+use {
+    crate::ViolationKind,
+    core::panic::Location,
+    std::{
+        collections::BTreeMap,
+        sync::{Mutex, PoisonError},
+        vec::Vec,
+    },
+};
+
+/// The number of invariant violations of a single kind detected at a single call site.
+///
+/// See [`audit_counters()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditCounter {
+    location: &'static Location<'static>,
+    kind: ViolationKind,
+    count: u64,
+}
+
+impl AuditCounter {
+    /// Returns the source location of the (user) code which violated the invariant.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the kind of the violation.
+    pub fn kind(&self) -> ViolationKind {
+        self.kind
+    }
+
+    /// Returns how many times the violation was detected.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+type Counters = BTreeMap<(&'static Location<'static>, ViolationKind), u64>;
+
+static COUNTERS: Mutex<Counters> = Mutex::new(BTreeMap::new());
+
+fn counters() -> std::sync::MutexGuard<'static, Counters> {
+    // The counters are always left in a consistent state, so it's fine to ignore the poisoning.
+    COUNTERS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Increments the counter for the violation of `kind` at `location`.
+pub(crate) fn record_violation(kind: ViolationKind, location: &'static Location<'static>) {
+    *counters().entry((location, kind)).or_default() += 1;
+}
+
+/// Returns the current values of the counters of the invariant violations detected so far,
+/// one per call site and violation kind, sorted by the call site.
+///
+/// Only available with the `audit` feature, which counts the violations detected by the debug checks,
+/// and then lets them fail like without the feature - via the safe fallback, i.e. the checked operation
+/// (e.g. `slice[index]`, [`Option::unwrap()`] or [`unreachable!()`]) instead of the unchecked one,
+/// so the violation never leads to UB, but panics with a std-like message
+/// (or aborts the process with the `abort-on-violation` feature / [`CheckPolicy::Abort`](crate::CheckPolicy::Abort)).
+/// E.g. a canary build may catch the panics (or let the affected thread / request die),
+/// and then dump the counters to find the call sites which are not yet safe to be left unchecked.
+///
+/// NOTE: the violations are only detected if the checks are performed, so to audit a release build
+/// combine the feature with `always-check` (or `sampling` / `runtime-policy`).
+pub fn audit_counters() -> Vec<AuditCounter> {
+    counters()
+        .iter()
+        .map(|(&(location, kind), &count)| AuditCounter {
+            location,
+            kind,
+            count,
+        })
+        .collect()
+}
+
+/// Same as [`audit_counters()`], but also resets all counters.
+pub fn take_audit_counters() -> Vec<AuditCounter> {
+    let counters = core::mem::take(&mut *counters());
+    counters
+        .into_iter()
+        .map(|((location, kind), count)| AuditCounter {
+            location,
+            kind,
+            count,
+        })
+        .collect()
+}
+
+#[cfg(all(
+    test,
+    miniunchecked_checks_enabled,
+    not(feature = "abort-on-violation")
+))]
+mod tests {
+    use {
+        super::*,
+        crate::{OptionExt, SliceExt},
+        std::panic,
+    };
+
+    #[test]
+    fn audit_counters() {
+        let slice = [2, 3, 4];
+        let line = line!() + 2;
+        for index in 1..5 {
+            let _ = panic::catch_unwind(|| unsafe { *slice.get_unchecked_dbg(index) });
+        }
+        let line_2 = line!() + 1;
+        let _ = panic::catch_unwind(|| unsafe { None::<i32>.unwrap_unchecked_dbg() });
+
+        assert_eq!(
+            counters(&[line, line_2]),
+            [
+                (line, ViolationKind::IndexOutOfBounds, 2),
+                (line_2, ViolationKind::NoneUnwrapped, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn safe_fallback() {
+        let slice = [2, 3, 4];
+        let index = 3;
+
+        // The violation is counted and execution goes on via the checked operation,
+        // which panics exactly like the standard library's does.
+        let line = line!() + 1;
+        let result = panic::catch_unwind(|| unsafe { *slice.get_unchecked_dbg(index) });
+        let std_result = panic::catch_unwind(|| slice[index]);
+        assert_eq!(
+            *result.unwrap_err().downcast::<String>().unwrap(),
+            *std_result.unwrap_err().downcast::<String>().unwrap()
+        );
+        assert_eq!(
+            counters(&[line]),
+            [(line, ViolationKind::IndexOutOfBounds, 1)]
+        );
+
+        assert_eq!(unsafe { *slice.get_unchecked_dbg(2) }, 4);
+        assert_eq!(unsafe { Some(7).unwrap_unchecked_dbg() }, 7);
+    }
+
+    /// Returns the counters of the violations at the `lines` of this file
+    /// (as other tests may run in parallel and record their own violations), as tuples of the line, kind and count.
+    fn counters(lines: &[u32]) -> Vec<(u32, ViolationKind, u64)> {
+        super::audit_counters()
+            .into_iter()
+            .filter(|counter| {
+                counter.location().file() == file!() && lines.contains(&counter.location().line())
+            })
+            .map(|counter| (counter.location().line(), counter.kind(), counter.count()))
+            .collect()
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(any(
+    all(miniunchecked_checks = "on", miniunchecked_checks = "off"),
+    all(miniunchecked_checks = "on", miniunchecked_checks = "ub_checks"),
+    all(miniunchecked_checks = "off", miniunchecked_checks = "ub_checks"),
+    all(miniunchecked_checks = "auto", miniunchecked_checks = "on"),
+    all(miniunchecked_checks = "auto", miniunchecked_checks = "off"),
+    all(miniunchecked_checks = "auto", miniunchecked_checks = "ub_checks"),
+))]
+compile_error!("`--cfg miniunchecked_checks` may only be specified once");
+
+/// Whether the debug checks are performed, i.e. whether [`unreachable_dbg()`](fn@crate::unreachable_dbg) and friends,
+/// as well as all extension trait methods, panic on invariant violations (`true`),
+/// or assume the invariants hold and use the unchecked operations (`false`).
+///
+/// Resolved at compile time by the build script, in order of precedence:
+/// - `--cfg miniunchecked_checks="on"` / `--cfg miniunchecked_checks="off"` rustc flag (e.g. via `RUSTFLAGS`)
+///   forces the checks on / off;
+/// - instrumented builds - running under [Miri](https://github.com/rust-lang/miri) (`cfg(miri)`),
+///   fuzzing (`cfg(fuzzing)`, set by e.g. `cargo fuzz`) or built with a sanitizer (`-Zsanitizer=...`) -
+///   force the checks on, so that an invariant violation is reported with the same diagnostics as in debug configuration
+///   instead of as opaque UB;
+/// - `--cfg miniunchecked_checks="ub_checks"` rustc flag makes the checks follow `cfg(ub_checks)` (see below);
+/// - `never-check` / `always-check` cargo feature forces the checks off / on
+///   (`never-check` takes precedence, so that e.g. `--all-features` builds have the checks off);
+/// - `ub-checks` cargo feature makes the checks follow `cfg(ub_checks)` (see below);
+/// - otherwise (or with `--cfg miniunchecked_checks="auto"`) the checks follow `debug_assertions`,
+///   i.e. are performed in debug configuration and when running tests.
+///
+/// `cfg(ub_checks)` is what the standard library uses to decide whether to check the preconditions
+/// of its own unsafe functions, like [`get_unchecked()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked).
+/// It defaults to `debug_assertions`, but may be overridden with the `-Zub-checks` rustc flag.
+/// Following it means the checks in this crate are performed exactly when the standard library's are.
+/// On toolchains which do not support `cfg(ub_checks)` (e.g. stable ones) this falls back to `debug_assertions`.
+///
+/// With the `runtime-policy` feature this only selects the `CheckPolicy::DEFAULT`,
+/// which may be overridden at runtime.
+pub const CHECKS_ENABLED: bool = cfg!(miniunchecked_checks_enabled);
+
+/// Whether the debug checks are performed for the current call - always if [`CHECKS_ENABLED`]
+/// (or, with the `runtime-policy` feature, unless the [`check_policy()`](crate::check_policy) is [`Off`](crate::CheckPolicy::Off)),
+/// otherwise only for the sampled calls (with the `sampling` feature, see [`set_sample_rate()`](crate::set_sample_rate)).
+///
+/// The extension trait methods use the unchecked operations directly if this returns `false`.
+#[inline(always)]
+pub(crate) fn check() -> bool {
+    checks_enabled() || sampled()
+}
+
+#[cfg(feature = "runtime-policy")]
+#[inline(always)]
+fn checks_enabled() -> bool {
+    crate::policy::policy_check()
+}
+
+#[cfg(not(feature = "runtime-policy"))]
+#[inline(always)]
+const fn checks_enabled() -> bool {
+    CHECKS_ENABLED
+}
+
+/// Whether the current call is sampled for checks
+/// (unless the checks are explicitly forced off via `--cfg miniunchecked_checks="off"` or the `never-check` feature).
+#[cfg(feature = "sampling")]
+#[inline(always)]
+fn sampled() -> bool {
+    !cfg!(any(miniunchecked_checks = "off", feature = "never-check")) && crate::sampling::sample()
+}
+
+#[cfg(not(feature = "sampling"))]
+#[inline(always)]
+const fn sampled() -> bool {
+    false
+}
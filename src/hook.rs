@@ -0,0 +1,276 @@
+use {
+    crate::Violation,
+    core::{fmt, panic::Location},
+};
+
+#[cfg(target_has_atomic = "ptr")]
+use core::{
+    mem, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Information about an invariant violation detected by the debug checks, passed to the violation hook.
+///
+/// Its [`Display`](fmt::Display) implementation produces the description of the violation
+/// (which is also used as the panic message, if any), including the custom error message, if any,
+/// e.g. "index out of bounds: the len is 3 but the index is 3: invalid index"
+/// (or only the message for the [`Option::expect()`]-like violations, e.g. [`Violation::NoneExpected`], and [`Violation::Unwrapped`]).
+///
+/// See [`set_violation_hook()`].
+#[derive(Clone, Copy, Debug)]
+pub struct ViolationInfo<'a> {
+    violation: &'a Violation<'a>,
+    message: Option<fmt::Arguments<'a>>,
+    location: &'a Location<'a>,
+}
+
+impl<'a> ViolationInfo<'a> {
+    pub(crate) fn new(
+        violation: &'a Violation<'a>,
+        message: Option<fmt::Arguments<'a>>,
+        location: &'a Location<'a>,
+    ) -> Self {
+        Self {
+            violation,
+            message,
+            location,
+        }
+    }
+
+    /// Returns the kind of the violation.
+    pub fn violation(&self) -> &'a Violation<'a> {
+        self.violation
+    }
+
+    /// Returns the custom error message, if any was provided.
+    ///
+    /// For [`Violation::ErrUnwrapped`] / [`Violation::OkUnwrapped`] reported by the methods which print the unwrapped error / value
+    /// (e.g. [`ResultDebugExt::unwrap_debug_unchecked_dbg()`](crate::ResultDebugExt::unwrap_debug_unchecked_dbg)),
+    /// the message starts with its [`Debug`](fmt::Debug) representation, followed by the custom error message (if any was provided),
+    /// separated by `": "`. For [`Violation::ErrExpected`] / [`Violation::OkExpected`] the order is reversed,
+    /// like in the messages of [`Result::expect()`] / [`Result::expect_err()`].
+    /// For [`Violation::Unwrapped`] the message starts with the failure description of the unwrapped value instead.
+    pub fn message(&self) -> Option<fmt::Arguments<'a>> {
+        self.message
+    }
+
+    /// Returns the source location of the (user) code which violated the invariant.
+    pub fn location(&self) -> &'a Location<'a> {
+        self.location
+    }
+}
+
+impl fmt::Display for ViolationInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(message) = self.message {
+            if self.violation.is_described_by_message() {
+                write!(f, "{}", message)
+            } else {
+                write!(f, "{}: {}", self.violation, message)
+            }
+        } else {
+            write!(f, "{}", self.violation)
+        }
+    }
+}
+
+/// A violation hook. See [`set_violation_hook()`].
+pub type ViolationHook = fn(&ViolationInfo<'_>);
+
+#[cfg(target_has_atomic = "ptr")]
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers a process-global violation hook, replacing the previously registered one (if any).
+///
+/// The hook is called whenever the debug checks detect an invariant violation, i.e. when [`unreachable_dbg()`](fn@crate::unreachable_dbg)
+/// and friends are reached, or an extension trait method encounters [`None`] / [`Err`] / an invalid index,
+/// with the description of the violation and the source location of the (user) code which violated the invariant,
+/// before the panic (or abort, see the `abort-on-violation` feature) occurs. E.g. may be used to log the violation to telemetry before the process dies.
+///
+/// NOTE: the hook is only called when the checks are enabled (see [`CHECKS_ENABLED`](crate::CHECKS_ENABLED))
+/// or the call is sampled (with the `sampling` feature),
+/// as otherwise the violations are not detected.
+///
+/// The hook is not reentrant: if the hook itself violates an invariant checked by this crate on the same thread,
+/// the nested violation is not reported to the hook (but still panics).
+/// Without the `std` feature the reentrancy is tracked process-wide, so violations on other threads
+/// which happen while the hook is running are not reported to the hook either.
+#[cfg(target_has_atomic = "ptr")]
+pub fn set_violation_hook(hook: ViolationHook) {
+    HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Unregisters the process-global violation hook, returning it (if any was registered).
+///
+/// See [`set_violation_hook()`].
+#[cfg(target_has_atomic = "ptr")]
+pub fn take_violation_hook() -> Option<ViolationHook> {
+    let hook = HOOK.swap(ptr::null_mut(), Ordering::AcqRel);
+    // SAFETY: only ever set to a valid `ViolationHook` by `set_violation_hook()`.
+    (!hook.is_null()).then(|| unsafe { mem::transmute::<*mut (), ViolationHook>(hook) })
+}
+
+/// Calls the registered violation hook (if any), unless already called on this thread.
+#[cfg(target_has_atomic = "ptr")]
+#[inline]
+pub(crate) fn call_violation_hook(
+    violation: &Violation<'_>,
+    message: Option<fmt::Arguments<'_>>,
+    location: &Location<'_>,
+) {
+    let hook = HOOK.load(Ordering::Acquire);
+
+    if hook.is_null() {
+        return;
+    }
+
+    // SAFETY: only ever set to a valid `ViolationHook` by `set_violation_hook()`.
+    let hook = unsafe { mem::transmute::<*mut (), ViolationHook>(hook) };
+
+    if let Some(_guard) = ReentrancyGuard::enter() {
+        hook(&ViolationInfo::new(violation, message, location));
+    }
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+#[inline]
+pub(crate) fn call_violation_hook(
+    _violation: &Violation<'_>,
+    _message: Option<fmt::Arguments<'_>>,
+    _location: &Location<'_>,
+) {
+}
+
+/// Prevents the violation hook from being called recursively.
+/// Resets when dropped, so that a hook which panics does not stay "entered".
+#[cfg(target_has_atomic = "ptr")]
+struct ReentrancyGuard;
+
+#[cfg(all(target_has_atomic = "ptr", any(feature = "std", test)))]
+std::thread_local! {
+    static IN_HOOK: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+#[cfg(all(target_has_atomic = "ptr", not(any(feature = "std", test))))]
+static IN_HOOK: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+#[cfg(target_has_atomic = "ptr")]
+impl ReentrancyGuard {
+    #[cfg(any(feature = "std", test))]
+    fn enter() -> Option<Self> {
+        (!IN_HOOK.replace(true)).then_some(Self)
+    }
+
+    #[cfg(not(any(feature = "std", test)))]
+    fn enter() -> Option<Self> {
+        (!IN_HOOK.swap(true, Ordering::Acquire)).then_some(Self)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl Drop for ReentrancyGuard {
+    #[cfg(any(feature = "std", test))]
+    fn drop(&mut self) {
+        IN_HOOK.set(false);
+    }
+
+    #[cfg(not(any(feature = "std", test)))]
+    fn drop(&mut self) {
+        IN_HOOK.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(all(
+    test,
+    miniunchecked_checks_enabled,
+    not(feature = "abort-on-violation")
+))]
+mod tests {
+    use {
+        super::*,
+        crate::{OptionExt, SliceExt, unreachable_dbg},
+        std::{cell::RefCell, panic},
+    };
+
+    thread_local! {
+        static VIOLATIONS: RefCell<Vec<(String, u32)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record_violation(info: &ViolationInfo<'_>) {
+        let message = info.to_string();
+
+        VIOLATIONS.with(|violations| {
+            violations
+                .borrow_mut()
+                .push((message.clone(), info.location().line()))
+        });
+
+        if message.ends_with("reentrant") {
+            let _ = unsafe { None::<i32>.unwrap_unchecked_dbg() };
+        }
+    }
+
+    fn take_violations() -> Vec<(String, u32)> {
+        VIOLATIONS.with(|violations| violations.take())
+    }
+
+    #[test]
+    fn violation_hook() {
+        set_violation_hook(record_violation);
+
+        let slice = [2, 3, 4];
+        let line = line!() + 1;
+        assert!(panic::catch_unwind(|| unsafe { slice.get_unchecked_dbg(3) }).is_err());
+        assert_eq!(
+            take_violations(),
+            [(
+                "index out of bounds: the len is 3 but the index is 3".to_string(),
+                line
+            )]
+        );
+
+        let line = line!() + 1;
+        assert!(panic::catch_unwind(|| unsafe { unreachable_dbg() }).is_err());
+        assert_eq!(
+            take_violations(),
+            [("internal error: entered unreachable code".to_string(), line)]
+        );
+
+        let none: Option<i32> = None;
+        let line = line!() + 1;
+        assert!(panic::catch_unwind(|| unsafe { none.expect_unchecked_dbg("none") }).is_err());
+        assert_eq!(take_violations(), [("none".to_string(), line)]);
+
+        // The nested violation in the hook panics, but is not reported to the hook.
+        let line = line!() + 1;
+        let result = panic::catch_unwind(|| unsafe { crate::unreachable_dbg_msg("reentrant") });
+        assert!(
+            result
+                .unwrap_err()
+                .downcast::<String>()
+                .unwrap()
+                .ends_with("called `Option::unwrap()` on a `None` value")
+        );
+        assert_eq!(
+            take_violations(),
+            [(
+                "internal error: entered unreachable code: reentrant".to_string(),
+                line
+            )]
+        );
+
+        // The reentrancy guard is reset after the hook panics.
+        let line = line!() + 1;
+        assert!(panic::catch_unwind(|| unsafe { unreachable_dbg() }).is_err());
+        assert_eq!(
+            take_violations(),
+            [("internal error: entered unreachable code".to_string(), line)]
+        );
+
+        assert!(take_violation_hook().is_some());
+        assert!(take_violation_hook().is_none());
+
+        assert!(panic::catch_unwind(|| unsafe { unreachable_dbg() }).is_err());
+        assert!(take_violations().is_empty());
+    }
+}
@@ -6,40 +6,118 @@
 //!
 //! A middle ground between someting like
 //! - calling [`Option::unwrap()`] / [`Result::unwrap()`] / slice/string square brackets indexing operator, or using [`unreachable!`],
-//! which always panic if [`None`] / [`Err`] / out of bounds / reached, and
+//!   which always panic if [`None`] / [`Err`] / out of bounds / reached, and
 //! - unsafe [`Option::unwrap_unchecked()`] [`Result::unwrap_unchecked()`] / [`[T]::get_unchecked()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked),
-//! or using [`unreachable_unchecked()`](std::hint::unreachable_unchecked),
-//! which never panic and lead to UB when [`None`] / [`Err`] / out of bounds,
+//!   or using [`unreachable_unchecked()`](core::hint::unreachable_unchecked),
+//!   which never panic and lead to UB when [`None`] / [`Err`] / out of bounds,
 //!
 //! an operation which does unsafe access in release configuration, for optimal codegen if the invariants are maintained by other means,
 //! but also panics on [`None`] / [`Err`] / out of bounds index in debug configuration / when running tests.
+//!
+//! # Features
+//!
+//! The crate is `#![no_std]` by default and only depends on [`core`].
+//!
+//! - `std` - links the [`std`](https://doc.rust-lang.org/std/) crate.
+//! - `always-check` - performs the debug checks regardless of `debug_assertions`.
+//! - `never-check` - never performs the debug checks, regardless of `debug_assertions`. Takes precedence over `always-check`.
+//! - `ub-checks` - performs the debug checks exactly when the standard library checks the preconditions of its unsafe functions
+//!   (`cfg(ub_checks)`, controlled by `-Zub-checks`), falling back to `debug_assertions` on toolchains which do not support it.
+//! - `abort-on-violation` - when the debug checks detect an invariant violation, writes the description of the violation to stderr
+//!   and aborts the process (via [`std::process::abort()`](https://doc.rust-lang.org/std/process/fn.abort.html)) instead of panicking.
+//!   Useful with `panic=abort`, or when the checks may fail in code which must not unwind, like `extern "C"` callbacks. Implies `std`.
+//! - `audit` - counts the invariant violations detected by the debug checks per call site and violation kind,
+//!   and then lets them fail like without the feature - via the safe fallback, the checked operation,
+//!   which panics like the standard library's (or aborts the process with `abort-on-violation`).
+//!   The counters may be inspected with `audit_counters()`. Combine with `always-check` (or `sampling` / `runtime-policy`)
+//!   to audit release builds. Implies `std`.
+//! - `sampling` - if the debug checks are otherwise disabled (but not explicitly forced off via `never-check`
+//!   or `--cfg miniunchecked_checks="off"`), performs them for 1 in N calls on each thread
+//!   and uses the unchecked operations otherwise. N is set at compile time via the `MINIUNCHECKED_SAMPLE_RATE` environment variable
+//!   (`1000` by default), or at runtime via `set_sample_rate()`. Implies `std`.
+//! - `runtime-policy` - selects what the debug checks do at runtime via `CheckPolicy` (`Off` / `Panic` / `Abort` / `Log`),
+//!   read once from the `MINIUNCHECKED` environment variable (e.g. `MINIUNCHECKED=panic`) or set via `set_check_policy()`.
+//!   The compile-time configuration only selects the default policy. Implies `std`.
+//! - `macros` - enables the `#[unchecked_dbg]` attribute macro, which rewrites indexing, `.unwrap()` and `unreachable!()`
+//!   in a function body to their debug-checked unchecked counterparts, and the `#[derive(UncheckedVariants)]` macro,
+//!   which generates debug-checked unchecked accessors for the variants of an enum. Adds a dependency on the `miniunchecked-macros` crate.
+//!
+//! Throughout the documentation "debug configuration" means "the checks are enabled" - see [`CHECKS_ENABLED`]
+//! for how this is determined and how to override it via the features above or the `--cfg miniunchecked_checks` rustc flag.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+// Tests deliberately use invalid constant indices / ranges to compare against std behaviour.
+#![cfg_attr(
+    test,
+    allow(
+        clippy::out_of_bounds_indexing,
+        clippy::reversed_empty_ranges,
+        clippy::clone_on_copy
+    )
+)]
+
+#[cfg(feature = "audit")]
+mod audit;
+mod config;
+mod hook;
+mod message;
 mod option;
+#[cfg(feature = "runtime-policy")]
+mod policy;
 mod result;
+#[cfg(feature = "sampling")]
+mod sampling;
 mod slice;
 mod str;
+mod unwrap;
+mod violation;
+
+#[cfg(feature = "audit")]
+pub use audit::*;
+#[cfg(feature = "runtime-policy")]
+pub use policy::*;
+#[cfg(feature = "sampling")]
+pub use sampling::*;
+pub use {
+    crate::str::{DbgStr, StrExt},
+    config::*,
+    hook::*,
+    message::*,
+    option::*,
+    result::*,
+    slice::*,
+    unwrap::*,
+    violation::{IndexError, Indexed, Violation, ViolationKind},
+};
+
+#[cfg(feature = "macros")]
+pub use miniunchecked_macros::{UncheckedVariants, unchecked_dbg};
 
-pub use {crate::str::StrExt, option::*, result::*, slice::*};
+use {
+    config::check,
+    violation::{violated, violated_msg},
+};
 
 /// An alternative function to the [`unreachable!`] macro which panics in debug configuration (like [`unreachable!`] does),
-/// but doesn't in release configuration (like [`unreachable_unchecked()`](std::hint::unreachable_unchecked)).
+/// but doesn't in release configuration (like [`unreachable_unchecked()`](core::hint::unreachable_unchecked)).
 ///
 /// Also see [`unreachable_dbg!`].
 ///
 /// # Safety
 ///
-/// See [`unreachable_unchecked()`](std::hint::unreachable_unchecked) documentation.
+/// See [`unreachable_unchecked()`](core::hint::unreachable_unchecked) documentation.
 #[inline]
+#[track_caller]
 pub unsafe fn unreachable_dbg() -> ! {
-    if cfg!(debug_assertions) {
-        unreachable!()
+    if check() {
+        unreachable_violated(None)
     } else {
-        unsafe { std::hint::unreachable_unchecked() }
+        unsafe { core::hint::unreachable_unchecked() }
     }
 }
 
 /// An alternative function to the [`unreachable!`] macro which panics in debug configuration (like [`unreachable!`] does),
-/// but doesn't in release configuration (like [`unreachable_unchecked()`](std::hint::unreachable_unchecked)).
+/// but doesn't in release configuration (like [`unreachable_unchecked()`](core::hint::unreachable_unchecked)).
 ///
 /// Accepts an error message as a string literal.
 ///
@@ -47,18 +125,19 @@ pub unsafe fn unreachable_dbg() -> ! {
 ///
 /// # Safety
 ///
-/// See [`unreachable_unchecked()`](std::hint::unreachable_unchecked) documentation.
+/// See [`unreachable_unchecked()`](core::hint::unreachable_unchecked) documentation.
 #[inline]
+#[track_caller]
 pub unsafe fn unreachable_dbg_msg(msg: &'static str) -> ! {
-    if cfg!(debug_assertions) {
-        unreachable!("{}", msg)
+    if check() {
+        unreachable_violated(Some(msg))
     } else {
-        unsafe { std::hint::unreachable_unchecked() }
+        unsafe { core::hint::unreachable_unchecked() }
     }
 }
 
 /// An alternative function to the [`unreachable!`] macro which panics in debug configuration (like [`unreachable!`] does),
-/// but doesn't in release configuration (like [`unreachable_unchecked()`](std::hint::unreachable_unchecked)).
+/// but doesn't in release configuration (like [`unreachable_unchecked()`](core::hint::unreachable_unchecked)).
 ///
 /// Accepts an error message as format arguments.
 ///
@@ -66,18 +145,37 @@ pub unsafe fn unreachable_dbg_msg(msg: &'static str) -> ! {
 ///
 /// # Safety
 ///
-/// See [`unreachable_unchecked()`](std::hint::unreachable_unchecked) documentation.
+/// See [`unreachable_unchecked()`](core::hint::unreachable_unchecked) documentation.
 #[inline]
-pub unsafe fn unreachable_dbg_fmt(fmt: std::fmt::Arguments<'_>) -> ! {
-    if cfg!(debug_assertions) {
-        unreachable!("{}", fmt)
+#[track_caller]
+pub unsafe fn unreachable_dbg_fmt(fmt: core::fmt::Arguments<'_>) -> ! {
+    if check() {
+        unreachable_violated_fmt(fmt)
     } else {
-        unsafe { std::hint::unreachable_unchecked() }
+        unsafe { core::hint::unreachable_unchecked() }
     }
 }
 
+/// Reports reaching unreachable code, with an optional custom error message.
+///
+/// Cold and out of line, so that only the branch is inlined into the callers of `unreachable_dbg()` and friends.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_violated(msg: Option<&'static str>) -> ! {
+    violated_msg(Violation::Unreachable, msg)
+}
+
+/// Same as [`unreachable_violated()`], but with a custom formatted error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_violated_fmt(fmt: core::fmt::Arguments<'_>) -> ! {
+    violated(Violation::Unreachable, Some(fmt))
+}
+
 /// An alternative to [`unreachable!`] macro which panics in debug configuration (like [`unreachable!`] does),
-/// but doesn't in release configuration (like [`unreachable_unchecked()`](std::hint::unreachable_unchecked)).
+/// but doesn't in release configuration (like [`unreachable_unchecked()`](core::hint::unreachable_unchecked)).
 ///
 /// Variants accept
 /// - no arguments,
@@ -88,7 +186,7 @@ pub unsafe fn unreachable_dbg_fmt(fmt: std::fmt::Arguments<'_>) -> ! {
 ///
 /// # Safety
 ///
-/// See [`unreachable_unchecked()`](std::hint::unreachable_unchecked) documentation.
+/// See [`unreachable_unchecked()`](core::hint::unreachable_unchecked) documentation.
 ///
 /// # Examples
 ///
@@ -105,32 +203,111 @@ pub unsafe fn unreachable_dbg_fmt(fmt: std::fmt::Arguments<'_>) -> ! {
 #[macro_export]
 macro_rules! unreachable_dbg {
     () => {
-        miniunchecked::unreachable_dbg()
+        $crate::unreachable_dbg()
     };
     ($msg:literal) => {
-        miniunchecked::unreachable_dbg_msg($msg)
+        $crate::unreachable_dbg_msg($msg)
     };
     ($fmt:expr, $($args:tt)*) => {
-        miniunchecked::unreachable_dbg_fmt(format_args!($fmt, $($args)*))
+        $crate::unreachable_dbg_fmt(::core::format_args!($fmt, $($args)*))
+    };
+}
+
+/// An alternative to a `match` which extracts the fields of the only variant an enum value is known to be,
+/// and reaches [`unreachable_dbg_fmt()`] in the other arms, i.e. panics in debug configuration if the value is of another variant,
+/// but doesn't check the variant in release configuration.
+///
+/// Accepts the value, the pattern of the expected variant and the expression to evaluate if it matches
+/// (as in `value, Pattern => output`), optionally followed by a custom error message as a string literal or format arguments.
+/// The panic message includes the [`Debug`](core::fmt::Debug) representation of the actual value
+/// (or its [`discriminant()`](core::mem::discriminant) if it doesn't implement [`Debug`](core::fmt::Debug)).
+///
+/// # Safety
+///
+/// See [`unreachable_unchecked()`](core::hint::unreachable_unchecked) documentation.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::unwrap_variant_dbg;
+///
+/// #[derive(Debug)]
+/// enum Shape {
+///     Circle { r: f32 },
+///     Rect { w: f32, h: f32 },
+/// }
+///
+/// let shape = Shape::Rect { w: 2.0, h: 3.0 };
+///
+/// let (w, h) = unsafe { unwrap_variant_dbg!(&shape, Shape::Rect { w, h } => (*w, *h)) };
+/// assert_eq!((w, h), (2.0, 3.0));
+///
+/// let area = unsafe { unwrap_variant_dbg!(shape, Shape::Rect { w, h } => w * h, "expected a rectangle") };
+/// assert_eq!(area, 6.0);
+/// ```
+#[macro_export]
+macro_rules! unwrap_variant_dbg {
+    ($value:expr, $pattern:pat => $output:expr $(,)?) => {
+        match $value {
+            $pattern => $output,
+            #[allow(unreachable_patterns)]
+            ref __miniunchecked_other => $crate::unreachable_dbg_fmt(::core::format_args!(
+                "expected `{}`, found {:?}",
+                ::core::stringify!($pattern),
+                {
+                    #[allow(unused_imports)]
+                    use $crate::__private::{DebugVariant as _, DiscriminantVariant as _};
+                    (&$crate::__private::Variant(__miniunchecked_other)).__miniunchecked_variant()
+                },
+            )),
+        }
+    };
+    ($value:expr, $pattern:pat => $output:expr, $msg:literal $(,)?) => {
+        $crate::unwrap_variant_dbg!($value, $pattern => $output, "{}", $msg)
+    };
+    ($value:expr, $pattern:pat => $output:expr, $fmt:expr, $($args:tt)*) => {
+        match $value {
+            $pattern => $output,
+            #[allow(unreachable_patterns)]
+            ref __miniunchecked_other => $crate::unreachable_dbg_fmt(::core::format_args!(
+                "expected `{}`, found {:?}: {}",
+                ::core::stringify!($pattern),
+                {
+                    #[allow(unused_imports)]
+                    use $crate::__private::{DebugVariant as _, DiscriminantVariant as _};
+                    (&$crate::__private::Variant(__miniunchecked_other)).__miniunchecked_variant()
+                },
+                ::core::format_args!($fmt, $($args)*),
+            )),
+        }
     };
 }
 
 /// A utility macro which calls [`OptionExt::unwrap_unchecked_dbg()`](OptionExt::unwrap_unchecked_dbg) / [`ResultExt::unwrap_unchecked_dbg()`](ResultExt::unwrap_unchecked_dbg)
-/// on a passed option or result, with or without a custom error message.
+/// on a passed option or result (or [`UncheckedUnwrap::unwrap_output_unchecked_dbg()`] on any other [`UncheckedUnwrap`] value),
+/// with or without a custom error message.
+///
+/// If the error of a result implements [`Debug`](core::fmt::Debug), calls [`ResultDebugExt::unwrap_debug_unchecked_dbg()`](ResultDebugExt::unwrap_debug_unchecked_dbg)
+/// instead, so that the panic message includes the error, like the one of [`Result::unwrap()`] does.
 ///
 /// Variants accept
 /// - no arguments,
 /// - an error message as a string literal, or
 /// - an error message as format arguments.
 ///
+/// Prefixing the result with `err:` calls [`ResultExt::unwrap_err_unchecked_dbg()`](ResultExt::unwrap_err_unchecked_dbg) instead
+/// (or [`ResultDebugExt::unwrap_err_debug_unchecked_dbg()`](ResultDebugExt::unwrap_err_debug_unchecked_dbg) if the value implements
+/// [`Debug`](core::fmt::Debug)), with the same variants.
+///
 /// # Safety
 ///
-/// See [`OptionExt::unwrap_unchecked_dbg()`](OptionExt::unwrap_unchecked_dbg) / [`ResultExt::unwrap_unchecked_dbg()`](ResultExt::unwrap_unchecked_dbg) documentation.
+/// See [`OptionExt::unwrap_unchecked_dbg()`](OptionExt::unwrap_unchecked_dbg) / [`ResultExt::unwrap_unchecked_dbg()`](ResultExt::unwrap_unchecked_dbg)
+/// / [`ResultExt::unwrap_err_unchecked_dbg()`](ResultExt::unwrap_err_unchecked_dbg) documentation.
 ///
 /// # Examples
 ///
 /// ```
-/// use miniunchecked::{ unwrap_unchecked_dbg, OptionExt };
+/// use miniunchecked::unwrap_unchecked_dbg;
 ///
 /// let x = Some(7);
 ///
@@ -151,16 +328,710 @@ macro_rules! unreachable_dbg {
 ///
 /// let val = unsafe { unwrap_unchecked_dbg!(Some(-7), "something went wrong, expected {}", -7) };
 /// assert_eq!(val, -7);
+///
+/// let err = unsafe { unwrap_unchecked_dbg!(err: "x".parse::<i32>(), "expected a parse error") };
+/// assert_eq!(err.to_string(), "invalid digit found in string");
 /// ```
 #[macro_export]
 macro_rules! unwrap_unchecked_dbg {
-    ($option_or_result:expr) => {
-        ($option_or_result).unwrap_unchecked_dbg()
+    (err: $result:expr) => {
+        match $result {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultErrKind as _, ResultErrKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_err_kind()
+                    .unwrap_err(__miniunchecked_value)
+            }
+        }
+    };
+    (err: $result:expr, $msg:literal) => {
+        match $result {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultErrKind as _, ResultErrKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_err_kind()
+                    .unwrap_err_msg(__miniunchecked_value, $msg)
+            }
+        }
+    };
+    (err: $result:expr, $fmt:expr, $($args:tt)*) => {
+        match $result {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultErrKind as _, ResultErrKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_err_kind()
+                    .unwrap_err_fmt(__miniunchecked_value, ::core::format_args!($fmt, $($args)*))
+            }
+        }
+    };
+    ($value:expr) => {
+        match $value {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultKind as _, UncheckedUnwrapKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_kind()
+                    .unwrap(__miniunchecked_value)
+            }
+        }
+    };
+    ($value:expr, $msg:literal) => {
+        match $value {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultKind as _, UncheckedUnwrapKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_kind()
+                    .unwrap_msg(__miniunchecked_value, $msg)
+            }
+        }
+    };
+    ($value:expr, $fmt:expr, $($args:tt)*) => {
+        match $value {
+            __miniunchecked_value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{DebugResultKind as _, UncheckedUnwrapKind as _};
+                (&$crate::__private::Unwrap(&__miniunchecked_value))
+                    .__miniunchecked_unwrap_kind()
+                    .unwrap_fmt(__miniunchecked_value, ::core::format_args!($fmt, $($args)*))
+            }
+        }
+    };
+}
+
+/// A utility macro which calls [`SliceExt::get_unchecked_dbg()`](SliceExt::get_unchecked_dbg) / [`StrExt::get_unchecked_dbg()`](StrExt::get_unchecked_dbg)
+/// on a passed slice or string (or anything which dereferences to one, e.g. a [`Vec`] or a [`String`]), with or without a custom error message.
+///
+/// Variants accept
+/// - no arguments,
+/// - an error message as a string literal, or
+/// - an error message as format arguments.
+///
+/// Also see [`get_unchecked_mut_dbg!`].
+///
+/// # Safety
+///
+/// See [`SliceExt::get_unchecked_dbg()`](SliceExt::get_unchecked_dbg) / [`StrExt::get_unchecked_dbg()`](StrExt::get_unchecked_dbg) documentation.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::get_unchecked_dbg;
+///
+/// let slice = [2, 3, 4];
+/// let id = 1;
+///
+/// let val = unsafe { get_unchecked_dbg!(slice, id) };
+/// assert_eq!(*val, 3);
+///
+/// let val = unsafe { get_unchecked_dbg!(slice, 1..3, "something went wrong") };
+/// assert_eq!(val, [3, 4]);
+///
+/// let val = unsafe { get_unchecked_dbg!(slice, id, "no entity with id {}", id) };
+/// assert_eq!(*val, 3);
+///
+/// let val = unsafe { get_unchecked_dbg!("föo", 1..3) };
+/// assert_eq!(val, "ö");
+/// ```
+///
+/// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+/// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+#[macro_export]
+macro_rules! get_unchecked_dbg {
+    ($slice_or_str:expr, $index:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_dbg($index)
+    }};
+    ($slice_or_str:expr, $index:expr, $msg:literal) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_dbg_msg($index, $msg)
+    }};
+    ($slice_or_str:expr, $index:expr, $fmt:expr, $($args:tt)*) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_dbg_fmt($index, ::core::format_args!($fmt, $($args)*))
+    }};
+}
+
+/// Same as [`get_unchecked_dbg!`], but calls [`SliceExt::get_unchecked_mut_dbg()`](SliceExt::get_unchecked_mut_dbg) / [`StrExt::get_unchecked_mut_dbg()`](StrExt::get_unchecked_mut_dbg).
+///
+/// # Safety
+///
+/// See [`SliceExt::get_unchecked_mut_dbg()`](SliceExt::get_unchecked_mut_dbg) / [`StrExt::get_unchecked_mut_dbg()`](StrExt::get_unchecked_mut_dbg) documentation.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::get_unchecked_mut_dbg;
+///
+/// let mut slice = [2, 3, 4];
+/// let id = 1;
+///
+/// unsafe { *get_unchecked_mut_dbg!(slice, id) = 7 };
+/// unsafe { *get_unchecked_mut_dbg!(slice, 2, "something went wrong") = 8 };
+/// unsafe { *get_unchecked_mut_dbg!(slice, 0, "no entity with id {}", 0) = 9 };
+/// assert_eq!(slice, [9, 7, 8]);
+/// ```
+#[macro_export]
+macro_rules! get_unchecked_mut_dbg {
+    ($slice_or_str:expr, $index:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_mut_dbg($index)
+    }};
+    ($slice_or_str:expr, $index:expr, $msg:literal) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_mut_dbg_msg($index, $msg)
+    }};
+    ($slice_or_str:expr, $index:expr, $fmt:expr, $($args:tt)*) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_mut_dbg_fmt($index, ::core::format_args!($fmt, $($args)*))
+    }};
+}
+
+/// A utility macro which accepts an ordinary (possibly chained) indexing expression,
+/// e.g. `grid[y][x]` or `buf[off..off + n]`, and performs each indexing step with
+/// [`SliceExt::get_unchecked_dbg()`](SliceExt::get_unchecked_dbg) / [`StrExt::get_unchecked_dbg()`](StrExt::get_unchecked_dbg),
+/// or with [`SliceExt::get_unchecked_mut_dbg()`](SliceExt::get_unchecked_mut_dbg) / [`StrExt::get_unchecked_mut_dbg()`](StrExt::get_unchecked_mut_dbg)
+/// if prefixed with `mut`.
+///
+/// Like the indexing expression, evaluates to a place, which may be read, borrowed or (with `mut`) assigned to.
+///
+/// The trailing `[...]` steps are indexed, everything before them is the indexed expression.
+/// In case an index is invalid, the error message contains the expression up to the failed step,
+/// e.g. "index out of bounds: the len is 3 but the index is 3: in `grid[y][x]`".
+///
+/// # Safety
+///
+/// See [`SliceExt::get_unchecked_dbg()`](SliceExt::get_unchecked_dbg) / [`StrExt::get_unchecked_dbg()`](StrExt::get_unchecked_dbg) documentation,
+/// which applies to each indexing step.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::index_dbg;
+///
+/// let mut grid = [[1, 2, 3], [4, 5, 6]];
+/// let (x, y) = (2, 1);
+///
+/// assert_eq!(unsafe { index_dbg!(grid[y][x]) }, 6);
+/// assert_eq!(unsafe { &index_dbg!(grid[y][..x]) }, [4, 5]);
+///
+/// unsafe { index_dbg!(mut grid[y][x]) = 7 };
+/// assert_eq!(grid, [[1, 2, 3], [4, 5, 7]]);
+///
+/// let s = "föo";
+/// assert_eq!(unsafe { &index_dbg!(s[1..3]) }, "ö");
+/// ```
+#[macro_export]
+macro_rules! index_dbg {
+    (mut $($expr:tt)+) => {
+        $crate::__index_dbg!(@split mut [] $($expr)+)
+    };
+    ($($expr:tt)+) => {
+        $crate::__index_dbg!(@split shared [] $($expr)+)
+    };
+}
+
+/// Implementation detail of [`index_dbg!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __index_dbg {
+    // Only `[index]` steps are left - index the expression collected so far.
+    (@split $mode:tt [$($base:tt)+] $([$($index:tt)+])+) => {
+        (*$crate::__index_dbg!(
+            @step $mode ($($base)+) {::core::stringify!($($base)+),} $([$($index)+])+
+        ))
+    };
+    // Otherwise the next token is a part of the indexed expression.
+    (@split $mode:tt [$($base:tt)*] $next:tt $($rest:tt)+) => {
+        $crate::__index_dbg!(@split $mode [$($base)* $next] $($rest)+)
     };
-    ($option_or_result:expr, $msg:literal) => {
-        ($option_or_result).unwrap_unchecked_dbg_msg($msg)
+    // `{...}` accumulates the pieces of the description of the expression indexed so far.
+    (@step $mode:tt ($($indexed:tt)+) {$($expr:tt)+} [$($index:tt)+] $($rest:tt)*) => {
+        $crate::__index_dbg!(
+            @step $mode
+            ($crate::__index_dbg!(
+                @get $mode ($($indexed)+),
+                ($($index)+),
+                ::core::concat!("in `", $($expr)+ "[", ::core::stringify!($($index)+), "]`")
+            ))
+            {$($expr)+ "[", ::core::stringify!($($index)+), "]",}
+            $($rest)*
+        )
     };
-    ($option_or_result:expr, $fmt:expr, $($args:tt)*) => {
-        ($option_or_result).unwrap_unchecked_dbg_fmt(format_args!($fmt, $($args)*))
+    (@step $mode:tt ($($indexed:tt)+) {$($expr:tt)+}) => {
+        $($indexed)+
     };
+    (@get shared $slice_or_str:expr, $index:expr, $msg:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_dbg_msg($index, $msg)
+    }};
+    (@get mut $slice_or_str:expr, $index:expr, $msg:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{SliceExt as _, StrExt as _};
+        ($slice_or_str).get_unchecked_mut_dbg_msg($index, $msg)
+    }};
+}
+
+/// Implementation details of the macros, including the [`unchecked_dbg`](https://docs.rs/miniunchecked-macros) attribute macro.
+#[doc(hidden)]
+pub mod __private {
+    use {
+        crate::{ResultDebugExt, ResultExt, UncheckedUnwrap},
+        core::{fmt, mem},
+    };
+
+    /// Mutably (re)borrows the slice / string an expression dereferences to,
+    /// auto-dereferencing it like the square brackets indexing operator does.
+    pub trait AsMutIndexed {
+        #[inline(always)]
+        fn __miniunchecked_as_mut(&mut self) -> &mut Self {
+            self
+        }
+    }
+
+    impl<T> AsMutIndexed for [T] {}
+
+    impl AsMutIndexed for str {}
+
+    /// A value of an unexpected variant, reported by [`unwrap_variant_dbg!`](crate::unwrap_variant_dbg).
+    ///
+    /// `(&Variant(value)).__miniunchecked_variant()` resolves to [`DebugVariant`] if the value implements [`Debug`](fmt::Debug),
+    /// as its method needs no autoref, and to [`DiscriminantVariant`] otherwise.
+    pub struct Variant<'a, T>(pub &'a T);
+
+    pub trait DebugVariant<'a, T> {
+        fn __miniunchecked_variant(&self) -> &'a T;
+    }
+
+    impl<'a, T> DebugVariant<'a, T> for Variant<'a, T>
+    where
+        T: fmt::Debug,
+    {
+        #[inline(always)]
+        fn __miniunchecked_variant(&self) -> &'a T {
+            self.0
+        }
+    }
+
+    pub trait DiscriminantVariant<T> {
+        fn __miniunchecked_variant(&self) -> mem::Discriminant<T>;
+    }
+
+    impl<T> DiscriminantVariant<T> for &Variant<'_, T> {
+        #[inline(always)]
+        fn __miniunchecked_variant(&self) -> mem::Discriminant<T> {
+            mem::discriminant(self.0)
+        }
+    }
+
+    /// A value unwrapped by [`unwrap_unchecked_dbg!`](crate::unwrap_unchecked_dbg).
+    ///
+    /// `(&Unwrap(&value)).__miniunchecked_unwrap_kind()` resolves to [`DebugResultKind`] if the value is a [`Result`]
+    /// whose error implements [`Debug`](fmt::Debug), as its method needs no autoref, and to [`UncheckedUnwrapKind`] otherwise.
+    /// Likewise, `(&Unwrap(&result)).__miniunchecked_unwrap_err_kind()` resolves to [`DebugResultErrKind`]
+    /// if the value of the result implements [`Debug`](fmt::Debug), and to [`ResultErrKind`] otherwise.
+    pub struct Unwrap<'a, U>(pub &'a U);
+
+    pub trait DebugResultKind {
+        #[inline(always)]
+        fn __miniunchecked_unwrap_kind(&self) -> DebugResultUnwrap {
+            DebugResultUnwrap
+        }
+    }
+
+    impl<T, E> DebugResultKind for Unwrap<'_, Result<T, E>> where E: fmt::Debug {}
+
+    pub trait UncheckedUnwrapKind {
+        #[inline(always)]
+        fn __miniunchecked_unwrap_kind(&self) -> OutputUnwrap {
+            OutputUnwrap
+        }
+    }
+
+    impl<U> UncheckedUnwrapKind for &Unwrap<'_, U> where U: UncheckedUnwrap {}
+
+    pub trait DebugResultErrKind {
+        #[inline(always)]
+        fn __miniunchecked_unwrap_err_kind(&self) -> DebugResultUnwrap {
+            DebugResultUnwrap
+        }
+    }
+
+    impl<T, E> DebugResultErrKind for Unwrap<'_, Result<T, E>> where T: fmt::Debug {}
+
+    pub trait ResultErrKind {
+        #[inline(always)]
+        fn __miniunchecked_unwrap_err_kind(&self) -> ResultUnwrap {
+            ResultUnwrap
+        }
+    }
+
+    impl<T, E> ResultErrKind for &Unwrap<'_, Result<T, E>> {}
+
+    /// Unwraps a [`Result`] via [`ResultDebugExt`], including the unexpected error / value in the panic message.
+    pub struct DebugResultUnwrap;
+
+    impl DebugResultUnwrap {
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap<T, E>(self, result: Result<T, E>) -> T
+        where
+            E: fmt::Debug,
+        {
+            unsafe { result.unwrap_debug_unchecked_dbg() }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_msg<T, E>(self, result: Result<T, E>, msg: &'static str) -> T
+        where
+            E: fmt::Debug,
+        {
+            unsafe { result.unwrap_debug_unchecked_dbg_msg(msg) }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_fmt<T, E>(self, result: Result<T, E>, fmt: fmt::Arguments<'_>) -> T
+        where
+            E: fmt::Debug,
+        {
+            unsafe { result.unwrap_debug_unchecked_dbg_fmt(fmt) }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err<T, E>(self, result: Result<T, E>) -> E
+        where
+            T: fmt::Debug,
+        {
+            unsafe { result.unwrap_err_debug_unchecked_dbg() }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err_msg<T, E>(self, result: Result<T, E>, msg: &'static str) -> E
+        where
+            T: fmt::Debug,
+        {
+            unsafe { result.unwrap_err_debug_unchecked_dbg_msg(msg) }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err_fmt<T, E>(self, result: Result<T, E>, fmt: fmt::Arguments<'_>) -> E
+        where
+            T: fmt::Debug,
+        {
+            unsafe { result.unwrap_err_debug_unchecked_dbg_fmt(fmt) }
+        }
+    }
+
+    /// Unwraps the error of a [`Result`] via [`ResultExt`].
+    pub struct ResultUnwrap;
+
+    impl ResultUnwrap {
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err<T, E>(self, result: Result<T, E>) -> E {
+            unsafe { result.unwrap_err_unchecked_dbg() }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err_msg<T, E>(self, result: Result<T, E>, msg: &'static str) -> E {
+            unsafe { result.unwrap_err_unchecked_dbg_msg(msg) }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_err_fmt<T, E>(
+            self,
+            result: Result<T, E>,
+            fmt: fmt::Arguments<'_>,
+        ) -> E {
+            unsafe { result.unwrap_err_unchecked_dbg_fmt(fmt) }
+        }
+    }
+
+    /// Unwraps any value via [`UncheckedUnwrap`].
+    pub struct OutputUnwrap;
+
+    impl OutputUnwrap {
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap<U>(self, value: U) -> U::Output
+        where
+            U: UncheckedUnwrap,
+        {
+            unsafe { value.unwrap_output_unchecked_dbg() }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_msg<U>(self, value: U, msg: &'static str) -> U::Output
+        where
+            U: UncheckedUnwrap,
+        {
+            unsafe { value.unwrap_output_unchecked_dbg_msg(msg) }
+        }
+
+        #[inline(always)]
+        #[track_caller]
+        pub unsafe fn unwrap_fmt<U>(self, value: U, fmt: fmt::Arguments<'_>) -> U::Output
+        where
+            U: UncheckedUnwrap,
+        {
+            unsafe { value.unwrap_output_unchecked_dbg_fmt(fmt) }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use {
+        super::*,
+        std::{cell::RefCell, panic, sync::Once},
+    };
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    thread_local! {
+        static PANIC_LOCATION: RefCell<Option<(String, u32)>> = const { RefCell::new(None) };
+    }
+
+    /// Runs `f`, which is expected to panic, and returns the file and line the panic was reported at.
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    pub(crate) fn panic_location<R>(f: impl FnOnce() -> R) -> (String, u32) {
+        static SET_HOOK: Once = Once::new();
+        SET_HOOK.call_once(|| {
+            let default_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                PANIC_LOCATION.with(|location| {
+                    *location.borrow_mut() = info
+                        .location()
+                        .map(|location| (location.file().to_string(), location.line()))
+                });
+                default_hook(info);
+            }));
+        });
+
+        assert!(panic::catch_unwind(panic::AssertUnwindSafe(f)).is_err());
+        PANIC_LOCATION
+            .with(|location| location.borrow_mut().take())
+            .expect("panic location was not recorded")
+    }
+
+    /// Runs `f`, which is expected to panic, and returns the panic message.
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    pub(crate) fn panic_message<R>(f: impl FnOnce() -> R) -> String {
+        let payload = panic::catch_unwind(panic::AssertUnwindSafe(f))
+            .err()
+            .expect("expected a panic");
+
+        match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => payload
+                .downcast_ref::<&str>()
+                .expect("the panic payload is not a string")
+                .to_string(),
+        }
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unreachable_dbg_location() {
+        let location = panic_location(|| unsafe { unreachable_dbg() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { unreachable_dbg_msg("unreachable") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { unreachable_dbg_fmt(format_args!("unreachable {}", 7)) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unreachable_dbg_macro_failure() {
+        assert_eq!(
+            panic_message(|| unsafe { unreachable_dbg!() }),
+            "internal error: entered unreachable code"
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unreachable_dbg!("unreachable") }),
+            "internal error: entered unreachable code: unreachable"
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unreachable_dbg!("unreachable {}", 7) }),
+            "internal error: entered unreachable code: unreachable 7"
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unreachable_dbg!("unreachable {}", 7) }),
+            panic_message(|| unreachable!("unreachable {}", 7))
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unreachable_dbg_macro_location() {
+        let location = panic_location(|| unsafe { unreachable_dbg!() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { unreachable_dbg!("unreachable") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { unreachable_dbg!("unreachable {}", 7) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    #[derive(Debug)]
+    enum Shape {
+        Circle { r: i32 },
+        Rect { w: i32, h: i32 },
+    }
+
+    enum Opaque {
+        First,
+        Second(#[allow(dead_code)] i32),
+    }
+
+    #[test]
+    fn unwrap_variant_dbg_success() {
+        let shape = Shape::Rect { w: 2, h: 3 };
+        assert_eq!(
+            unsafe { unwrap_variant_dbg!(&shape, Shape::Rect { w, h } => (*w, *h)) },
+            (2, 3)
+        );
+        assert_eq!(
+            unsafe {
+                unwrap_variant_dbg!(shape, Shape::Rect { w, .. } | Shape::Circle { r: w } => w)
+            },
+            2
+        );
+        assert!(unsafe {
+            unwrap_variant_dbg!(Opaque::First, Opaque::First => true, "expected the first")
+        });
+
+        let shape = Shape::Circle { r: 7 };
+        let opaque = Opaque::Second(7);
+        assert_eq!(
+            unsafe { unwrap_variant_dbg!(&shape, Shape::Circle { r } => *r) },
+            7
+        );
+        assert_eq!(
+            unsafe { unwrap_variant_dbg!(opaque, Opaque::Second(x) => x) },
+            7
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "internal error: entered unreachable code: expected `Shape::Rect { w, h }`, found Circle { r: 7 }"]
+    fn unwrap_variant_dbg_failure() {
+        let shape = Shape::Circle { r: 7 };
+        let _ = unsafe { unwrap_variant_dbg!(&shape, Shape::Rect { w, h } => (*w, *h)) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "internal error: entered unreachable code: expected `Shape::Rect { w, h }`, found Circle { r: 7 }: expected a rectangle of area 6"]
+    fn unwrap_variant_dbg_fmt_failure() {
+        let shape = Shape::Circle { r: 7 };
+        let _ = unsafe {
+            unwrap_variant_dbg!(shape, Shape::Rect { w, h } => w * h, "expected a rectangle of area {}", 6)
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "internal error: entered unreachable code: expected `Opaque::First`, found Discriminant(1): expected the first"]
+    fn unwrap_variant_dbg_discriminant_failure() {
+        let _ = unsafe {
+            unwrap_variant_dbg!(Opaque::Second(7), Opaque::First => 1, "expected the first")
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_variant_dbg_location() {
+        let opaque = Opaque::Second(7);
+        let location =
+            panic_location(|| unsafe { unwrap_variant_dbg!(opaque, Opaque::First => ()) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    struct Grid {
+        cells: Vec<Vec<i32>>,
+    }
+
+    #[test]
+    fn index_dbg_success() {
+        let mut grid = Grid {
+            cells: vec![vec![1, 2, 3], vec![4, 5, 6]],
+        };
+        let (x, y) = (2, 1);
+
+        assert_eq!(unsafe { index_dbg!(grid.cells[y][x]) }, 6);
+        assert_eq!(unsafe { index_dbg!(grid.cells[y - 1][x - 1]) }, 2);
+        assert_eq!(unsafe { &index_dbg!(grid.cells[y][1..=x]) }, [5, 6]);
+        assert_eq!(unsafe { &index_dbg!(grid.cells[0][1..][1]) }, &3);
+        assert_eq!(unsafe { &index_dbg!((&grid.cells[..])[y]) }, &[4, 5, 6]);
+        assert_eq!(unsafe { index_dbg!([7, 8][1]) }, 8);
+
+        unsafe { index_dbg!(mut grid.cells[y][x]) = 7 };
+        unsafe { index_dbg!(mut grid.cells[0][..2]).reverse() };
+        assert_eq!(grid.cells, [[2, 1, 3], [4, 5, 7]]);
+
+        let mut string = "föo".to_string();
+        assert_eq!(unsafe { &index_dbg!(string[1..3]) }, "ö");
+        unsafe { index_dbg!(mut string[3..]).make_ascii_uppercase() };
+        assert_eq!(string, "föO");
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 2 but the index is 2: in `grid[y + 1]`"]
+    fn index_dbg_failure_first_step() {
+        let grid = [[1, 2, 3], [4, 5, 6]];
+        let (x, y) = (2, 1);
+        let _ = unsafe { index_dbg!(grid[y + 1][x]) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "range end index 4 out of range for slice of length 3: in `grid[y][x..x + 2]`"]
+    fn index_dbg_failure_second_step() {
+        let grid = [[1, 2, 3], [4, 5, 6]];
+        let (x, y) = (2, 1);
+        let _ = unsafe { &index_dbg!(grid[y][x..x + 2]) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: in `strings[0][2..]`"]
+    fn index_dbg_mut_failure() {
+        let mut strings = ["föo".to_string()];
+        unsafe { index_dbg!(mut strings[0][2..]).make_ascii_uppercase() };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn index_dbg_location() {
+        let grid = [[1, 2, 3], [4, 5, 6]];
+
+        let location = panic_location(|| unsafe { index_dbg!(grid[1][3]) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
 }
@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// An optional custom error message, as accepted by [`SliceIndexExt`](crate::SliceIndexExt).
+///
+/// The message is only evaluated once an invariant violation is detected,
+/// so it costs nothing if the checks are not performed (or pass).
+///
+/// Implemented for
+/// - [`Option<&'static str>`] - a string literal, or no message,
+/// - [`fmt::Arguments`] - a formatted message, and
+/// - closures returning a [`Display`](fmt::Display) value - a lazily computed message.
+pub trait DbgMessage {
+    /// The type of the message, formatted with its [`Display`](fmt::Display) implementation.
+    type Message: fmt::Display;
+
+    /// Evaluates the message, if any.
+    fn into_message(self) -> Option<Self::Message>;
+}
+
+impl DbgMessage for Option<&'static str> {
+    type Message = &'static str;
+
+    #[inline]
+    fn into_message(self) -> Option<Self::Message> {
+        self
+    }
+}
+
+impl<'a> DbgMessage for fmt::Arguments<'a> {
+    type Message = fmt::Arguments<'a>;
+
+    #[inline]
+    fn into_message(self) -> Option<Self::Message> {
+        Some(self)
+    }
+}
+
+impl<F, D> DbgMessage for F
+where
+    F: FnOnce() -> D,
+    D: fmt::Display,
+{
+    type Message = D;
+
+    #[inline]
+    fn into_message(self) -> Option<Self::Message> {
+        Some(self())
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{
+    Violation,
+    config::check,
+    violation::{violated, violated_msg},
+};
+
 /// An extension trait for [`Option`] which provides alternatives to [`unwrap_unchecked()`](Option::unwrap_unchecked)
 /// which panic in debug configuration with an optional custom message.
 pub trait OptionExt<T> {
@@ -20,46 +26,105 @@ pub trait OptionExt<T> {
     /// # Safety
     ///
     /// See [`unwrap_unchecked()`](Option::unwrap_unchecked) documentation.
-    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: std::fmt::Arguments<'_>) -> T;
+    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T;
+
+    /// Alternative to [`unwrap_unchecked()`](Option::unwrap_unchecked) which panics in debug configuration if the [`Option`] is [`None`],
+    /// with the message `msg` only, like [`expect()`](Option::expect) does.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_unchecked()`](Option::unwrap_unchecked) documentation.
+    unsafe fn expect_unchecked_dbg(self, msg: &str) -> T;
 }
 
 impl<T> OptionExt<T> for Option<T> {
     #[inline]
+    #[track_caller]
     unsafe fn unwrap_unchecked_dbg(self) -> T {
-        self.unwrap_or_else(|| unreachable_dbg_msg(None))
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Some(val) => val,
+            None => unreachable_dbg_msg(None),
+        }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn unwrap_unchecked_dbg_msg(self, msg: &'static str) -> T {
-        self.unwrap_or_else(|| unreachable_dbg_msg(Some(msg)))
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Some(val) => val,
+            None => unreachable_dbg_msg(Some(msg)),
+        }
     }
 
     #[inline]
-    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: std::fmt::Arguments<'_>) -> T {
-        self.unwrap_or_else(|| unreachable_dbg_fmt(fmt))
+    #[track_caller]
+    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Some(val) => val,
+            None => unreachable_dbg_fmt(fmt),
+        }
     }
-}
 
-const ERR_STR: &'static str = "called `Option::unwrap()` on a `None` value";
+    #[inline]
+    #[track_caller]
+    unsafe fn expect_unchecked_dbg(self, msg: &str) -> T {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Some(val) => val,
+            None => unreachable_dbg_expect(msg),
+        }
+    }
+}
 
-#[inline]
+/// Reports the failed unwrap, with an optional custom error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
 fn unreachable_dbg_msg(msg: Option<&'static str>) -> ! {
-    if let Some(msg) = msg {
-        unreachable_dbg_fmt(format_args!("{}", msg))
-    } else {
-        unsafe { crate::unreachable_dbg_fmt(format_args!("{}", ERR_STR)) }
-    }
+    violated_msg(Violation::NoneUnwrapped, msg)
 }
 
-#[inline]
-fn unreachable_dbg_fmt(fmt: std::fmt::Arguments<'_>) -> ! {
-    unsafe { crate::unreachable_dbg_fmt(format_args!("{}: {}", ERR_STR, fmt)) }
+/// Same as [`unreachable_dbg_msg()`], but with a custom formatted error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_fmt(fmt: core::fmt::Arguments<'_>) -> ! {
+    violated(Violation::NoneUnwrapped, Some(fmt))
+}
+
+/// Reports the failed [`expect()`](Option::expect)-like unwrap.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_expect(msg: &str) -> ! {
+    violated(Violation::NoneExpected, Some(format_args!("{msg}")))
 }
 
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::unwrap_unchecked_dbg};
-
+    use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::{
+        tests::{panic_location, panic_message},
+        unwrap_unchecked_dbg,
+    };
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     fn returns_an_option(val: i32) -> Option<u8> {
         u8::try_from(val).ok()
     }
@@ -70,7 +135,7 @@ mod tests {
         assert_eq!(unsafe { x.unwrap_unchecked_dbg() }, 7)
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value"]
     fn unwrap_unchecked_dbg_failure() {
@@ -78,7 +143,20 @@ mod tests {
         let _ = unsafe { x.unwrap_unchecked_dbg() };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_unchecked_dbg_failure_matches_std() {
+        assert_eq!(
+            panic_message(|| unsafe { returns_an_option(-7).unwrap_unchecked_dbg() }),
+            panic_message(|| returns_an_option(-7).unwrap())
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unwrap_unchecked_dbg!(returns_an_option(-7)) }),
+            panic_message(|| returns_an_option(-7).unwrap())
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value"]
     fn unwrap_unchecked_dbg_macro_failure() {
@@ -86,7 +164,7 @@ mod tests {
         unsafe { unwrap_unchecked_dbg!(returns_an_option(-7)) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value: missing value"]
     fn unwrap_unchecked_dbg_msg_failure() {
@@ -94,7 +172,7 @@ mod tests {
         let _ = unsafe { x.unwrap_unchecked_dbg_msg("missing value") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value: missing value"]
     fn unwrap_unchecked_dbg_msg_macro_failure() {
@@ -102,7 +180,7 @@ mod tests {
         unsafe { unwrap_unchecked_dbg!(returns_an_option(-7), "missing value") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value: missing value (expected 7)"]
     fn unwrap_unchecked_dbg_fmt_failure() {
@@ -111,11 +189,54 @@ mod tests {
             unsafe { x.unwrap_unchecked_dbg_fmt(format_args!("missing value (expected {})", 7)) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Option::unwrap()` on a `None` value: missing value (expected -7)"]
     fn unwrap_unchecked_dbg_fmt_macro_failure() {
         unsafe { unwrap_unchecked_dbg!(returns_an_option(255), "this should succeed") };
         unsafe { unwrap_unchecked_dbg!(returns_an_option(-7), "missing value (expected {})", -7) };
     }
+
+    #[test]
+    fn expect_unchecked_dbg_success() {
+        let x = Some(7);
+        assert_eq!(unsafe { x.expect_unchecked_dbg("missing value") }, 7)
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "missing value"]
+    fn expect_unchecked_dbg_failure() {
+        let x: Option<i32> = None;
+        let _ = unsafe { x.expect_unchecked_dbg("missing value") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn expect_unchecked_dbg_failure_matches_std() {
+        assert_eq!(
+            panic_message(|| unsafe {
+                returns_an_option(-7).expect_unchecked_dbg("missing value")
+            }),
+            panic_message(|| returns_an_option(-7).expect("missing value"))
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_unchecked_dbg_location() {
+        let x: Option<i32> = None;
+
+        let location = panic_location(|| unsafe { x.unwrap_unchecked_dbg() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { x.unwrap_unchecked_dbg_msg("missing value") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { unwrap_unchecked_dbg!(x, "missing value {}", 7) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+        let location = panic_location(|| unsafe { x.expect_unchecked_dbg("missing value") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
 }
@@ -0,0 +1,131 @@
+use {
+    crate::CHECKS_ENABLED,
+    core::sync::atomic::{AtomicU8, Ordering},
+};
+
+/// What happens on a call to `unreachable_dbg()` and friends, as well as to any extension trait method,
+/// selected at runtime.
+///
+/// Only available with the `runtime-policy` feature. See [`set_check_policy()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum CheckPolicy {
+    /// The debug checks are not performed - the unchecked operations are used, like in release configuration.
+    Off = 0,
+    /// The debug checks are performed and panic on invariant violations, like in debug configuration.
+    Panic = 1,
+    /// The debug checks are performed; invariant violations are written to stderr and abort the process.
+    Abort = 2,
+    /// The debug checks are performed; invariant violations are written to stderr and then panic,
+    /// i.e. "log then panic" rather than "log and continue" - a violated invariant leaves no valid value to go on with.
+    /// With the `abort-on-violation` feature they abort the process instead, like with [`CheckPolicy::Abort`].
+    /// E.g. for production builds which catch the panics, but also want the violations logged.
+    Log = 3,
+}
+
+impl CheckPolicy {
+    /// The policy used if neither [`set_check_policy()`] was called, nor the `MINIUNCHECKED` environment variable is set:
+    /// [`CheckPolicy::Panic`] if [`CHECKS_ENABLED`], [`CheckPolicy::Off`] otherwise.
+    pub const DEFAULT: Self = if CHECKS_ENABLED {
+        CheckPolicy::Panic
+    } else {
+        CheckPolicy::Off
+    };
+
+    fn from_u8(policy: u8) -> Self {
+        match policy {
+            0 => CheckPolicy::Off,
+            1 => CheckPolicy::Panic,
+            2 => CheckPolicy::Abort,
+            3 => CheckPolicy::Log,
+            _ => unreachable!("invalid check policy"),
+        }
+    }
+
+    /// Parses the value of the `MINIUNCHECKED` environment variable (case-insensitive).
+    fn from_env_value(value: &str) -> Option<Self> {
+        [
+            ("off", CheckPolicy::Off),
+            ("panic", CheckPolicy::Panic),
+            ("abort", CheckPolicy::Abort),
+            ("log", CheckPolicy::Log),
+        ]
+        .into_iter()
+        .find_map(|(name, policy)| value.trim().eq_ignore_ascii_case(name).then_some(policy))
+    }
+}
+
+/// The policy has not been resolved yet - neither set via [`set_check_policy()`], nor read from the environment.
+/// Non-zero, so that [`CheckPolicy::Off`] is checked with a single comparison.
+const UNINIT: u8 = u8::MAX;
+
+static POLICY: AtomicU8 = AtomicU8::new(UNINIT);
+
+/// Sets the process-global check policy, overriding the `MINIUNCHECKED` environment variable.
+///
+/// Only available with the `runtime-policy` feature. See [`check_policy()`].
+pub fn set_check_policy(policy: CheckPolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Returns the process-global check policy.
+///
+/// Unless set via [`set_check_policy()`], it is read once from the `MINIUNCHECKED` environment variable
+/// (`off`, `panic`, `abort` or `log`, case-insensitive), falling back to [`CheckPolicy::DEFAULT`]
+/// if the variable is not set or has any other value.
+///
+/// Only available with the `runtime-policy` feature.
+pub fn check_policy() -> CheckPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        UNINIT => init_check_policy(),
+        policy => CheckPolicy::from_u8(policy),
+    }
+}
+
+/// Whether the debug checks are performed according to the check policy.
+///
+/// [`CheckPolicy::Off`] is a single load and comparison.
+#[inline(always)]
+pub(crate) fn policy_check() -> bool {
+    match POLICY.load(Ordering::Relaxed) {
+        0 => false,
+        UNINIT => init_check_policy() != CheckPolicy::Off,
+        _ => true,
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn init_check_policy() -> CheckPolicy {
+    let policy = std::env::var("MINIUNCHECKED")
+        .ok()
+        .and_then(|value| CheckPolicy::from_env_value(&value))
+        .unwrap_or(CheckPolicy::DEFAULT);
+
+    // Don't override the policy if it was set concurrently.
+    match POLICY.compare_exchange(UNINIT, policy as u8, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => policy,
+        Err(policy) => CheckPolicy::from_u8(policy),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_env_value() {
+        assert_eq!(CheckPolicy::from_env_value("off"), Some(CheckPolicy::Off));
+        assert_eq!(
+            CheckPolicy::from_env_value("Panic"),
+            Some(CheckPolicy::Panic)
+        );
+        assert_eq!(
+            CheckPolicy::from_env_value(" ABORT "),
+            Some(CheckPolicy::Abort)
+        );
+        assert_eq!(CheckPolicy::from_env_value("log"), Some(CheckPolicy::Log));
+        assert_eq!(CheckPolicy::from_env_value("strict"), None);
+        assert_eq!(CheckPolicy::from_env_value(""), None);
+    }
+}
@@ -1,6 +1,22 @@
+use {
+    crate::{
+        Violation,
+        config::check,
+        violation::{violated, violated_msg},
+    },
+    core::fmt,
+};
+
 /// An extension trait for [`Result`] which provides alternatives to [`unwrap_unchecked()`](Result::unwrap_unchecked)
-/// which panic in debug configuration with an optional custom message.
+/// and [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panic in debug configuration with an optional custom message.
+///
+/// Unlike [`Result::unwrap()`] / [`Result::unwrap_err()`], the panic message of [`unwrap_unchecked_dbg()`](ResultExt::unwrap_unchecked_dbg) and friends
+/// doesn't include the unexpected error / value, so they don't require it to implement [`Debug`](fmt::Debug).
+/// See [`ResultDebugExt`] for the variants which do.
 pub trait ResultExt<T> {
+    /// The type of the error.
+    type Error;
+
     /// Alternative to [`unwrap_unchecked()`](Result::unwrap_unchecked) which panics in debug configuration if the [`Result`] is [`Err`].
     ///
     /// # Safety
@@ -20,57 +36,412 @@ pub trait ResultExt<T> {
     /// # Safety
     ///
     /// See [`unwrap_unchecked()`](Result::unwrap_unchecked) documentation.
-    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: std::fmt::Arguments<'_>) -> T;
+    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`].
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_unchecked_dbg(self) -> Self::Error;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`], with a custom error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_unchecked_dbg_msg(self, msg: &'static str) -> Self::Error;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`], with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> Self::Error;
 }
 
 impl<T, E> ResultExt<T> for Result<T, E> {
+    type Error = E;
+
     #[inline]
+    #[track_caller]
     unsafe fn unwrap_unchecked_dbg(self) -> T {
-        self.unwrap_or_else(|_| unreachable_dbg_msg(None))
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(_) => unreachable_dbg_unwrap(Violation::ErrUnwrapped, None),
+        }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn unwrap_unchecked_dbg_msg(self, msg: &'static str) -> T {
-        self.unwrap_or_else(|_| unreachable_dbg_msg(Some(msg)))
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(_) => unreachable_dbg_unwrap(Violation::ErrUnwrapped, Some(msg)),
+        }
     }
 
     #[inline]
-    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: std::fmt::Arguments<'_>) -> T {
-        self.unwrap_or_else(|_| unreachable_dbg_fmt(fmt))
+    #[track_caller]
+    unsafe fn unwrap_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(_) => unreachable_dbg_unwrap_fmt(Violation::ErrUnwrapped, fmt),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_unchecked_dbg(self) -> E {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(_) => unreachable_dbg_unwrap(Violation::OkUnwrapped, None),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_unchecked_dbg_msg(self, msg: &'static str) -> E {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(_) => unreachable_dbg_unwrap(Violation::OkUnwrapped, Some(msg)),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> E {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(_) => unreachable_dbg_unwrap_fmt(Violation::OkUnwrapped, fmt),
+        }
     }
 }
 
-const ERR_STR: &'static str = "called `Result::unwrap()` on an `Err` value";
+/// An extension trait for [`Result`] which provides alternatives to [`unwrap_unchecked()`](Result::unwrap_unchecked)
+/// and [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panic in debug configuration with an optional custom message.
+///
+/// Unlike the ones of [`ResultExt`], the panic messages include the [`Debug`](fmt::Debug) representation of the unexpected value,
+/// like the ones of [`Result::unwrap()`] and friends do, e.g. ``called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }``,
+/// followed by the custom message, if any. Hence the methods require the type of that value to implement [`Debug`](fmt::Debug).
+pub trait ResultDebugExt<T, E> {
+    /// Alternative to [`unwrap_unchecked()`](Result::unwrap_unchecked) which panics in debug configuration if the [`Result`] is [`Err`],
+    /// including the error in the panic message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_unchecked()`](Result::unwrap_unchecked) documentation.
+    unsafe fn unwrap_debug_unchecked_dbg(self) -> T
+    where
+        E: fmt::Debug;
+
+    /// Alternative to [`unwrap_unchecked()`](Result::unwrap_unchecked) which panics in debug configuration if the [`Result`] is [`Err`],
+    /// including the error in the panic message, with a custom error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_unchecked()`](Result::unwrap_unchecked) documentation.
+    unsafe fn unwrap_debug_unchecked_dbg_msg(self, msg: &'static str) -> T
+    where
+        E: fmt::Debug;
+
+    /// Alternative to [`unwrap_unchecked()`](Result::unwrap_unchecked) which panics in debug configuration if the [`Result`] is [`Err`],
+    /// including the error in the panic message, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_unchecked()`](Result::unwrap_unchecked) documentation.
+    unsafe fn unwrap_debug_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T
+    where
+        E: fmt::Debug;
+
+    /// Alternative to [`unwrap_unchecked()`](Result::unwrap_unchecked) which panics in debug configuration if the [`Result`] is [`Err`],
+    /// with the message `msg` followed by the error, like [`expect()`](Result::expect) does.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_unchecked()`](Result::unwrap_unchecked) documentation.
+    unsafe fn expect_unchecked_dbg(self, msg: &str) -> T
+    where
+        E: fmt::Debug;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`],
+    /// including the value in the panic message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_debug_unchecked_dbg(self) -> E
+    where
+        T: fmt::Debug;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`],
+    /// including the value in the panic message, with a custom error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_debug_unchecked_dbg_msg(self, msg: &'static str) -> E
+    where
+        T: fmt::Debug;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`],
+    /// including the value in the panic message, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn unwrap_err_debug_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> E
+    where
+        T: fmt::Debug;
+
+    /// Alternative to [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) which panics in debug configuration if the [`Result`] is [`Ok`],
+    /// with the message `msg` followed by the value, like [`expect_err()`](Result::expect_err) does.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_err_unchecked()`](Result::unwrap_err_unchecked) documentation.
+    unsafe fn expect_err_unchecked_dbg(self, msg: &str) -> E
+    where
+        T: fmt::Debug;
+}
+
+impl<T, E> ResultDebugExt<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_debug_unchecked_dbg(self) -> T
+    where
+        E: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(val) => unreachable_dbg_msg(Violation::ErrUnwrapped, &val, None),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_debug_unchecked_dbg_msg(self, msg: &'static str) -> T
+    where
+        E: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(val) => unreachable_dbg_msg(Violation::ErrUnwrapped, &val, Some(msg)),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_debug_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> T
+    where
+        E: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(val) => unreachable_dbg_fmt(Violation::ErrUnwrapped, &val, fmt),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn expect_unchecked_dbg(self, msg: &str) -> T
+    where
+        E: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_unchecked() };
+        }
+
+        match self {
+            Ok(val) => val,
+            Err(val) => unreachable_dbg_expect(Violation::ErrExpected, &val, msg),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_debug_unchecked_dbg(self) -> E
+    where
+        T: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
 
-#[inline]
-fn unreachable_dbg_msg(msg: Option<&'static str>) -> ! {
-    if let Some(msg) = msg {
-        unreachable_dbg_fmt(format_args!("{}", msg))
-    } else {
-        unsafe { crate::unreachable_dbg_fmt(format_args!("{}", ERR_STR)) }
+        match self {
+            Err(val) => val,
+            Ok(val) => unreachable_dbg_msg(Violation::OkUnwrapped, &val, None),
+        }
     }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_debug_unchecked_dbg_msg(self, msg: &'static str) -> E
+    where
+        T: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(val) => unreachable_dbg_msg(Violation::OkUnwrapped, &val, Some(msg)),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_err_debug_unchecked_dbg_fmt(self, fmt: core::fmt::Arguments<'_>) -> E
+    where
+        T: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(val) => unreachable_dbg_fmt(Violation::OkUnwrapped, &val, fmt),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn expect_err_unchecked_dbg(self, msg: &str) -> E
+    where
+        T: fmt::Debug,
+    {
+        if !check() {
+            return unsafe { self.unwrap_err_unchecked() };
+        }
+
+        match self {
+            Err(val) => val,
+            Ok(val) => unreachable_dbg_expect(Violation::OkExpected, &val, msg),
+        }
+    }
+}
+
+/// Reports the `violation` of unwrapping an unexpected error / value, with an optional custom error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_unwrap(violation: Violation<'static>, msg: Option<&'static str>) -> ! {
+    violated_msg(violation, msg)
+}
+
+/// Same as [`unreachable_dbg_unwrap()`], but with a custom formatted error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_unwrap_fmt(violation: Violation<'static>, fmt: fmt::Arguments<'_>) -> ! {
+    violated(violation, Some(fmt))
+}
+
+/// Reports the `violation` of unwrapping the unexpected `val`, with an optional custom error message.
+///
+/// Takes the value as a trait object, so that it's not instantiated for each value type.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_msg(
+    violation: Violation<'static>,
+    val: &dyn fmt::Debug,
+    msg: Option<&'static str>,
+) -> ! {
+    match msg {
+        Some(msg) => violated(violation, Some(format_args!("{val:?}: {msg}"))),
+        None => violated(violation, Some(format_args!("{val:?}"))),
+    }
+}
+
+/// Same as [`unreachable_dbg_msg()`], but with a custom formatted error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_fmt(
+    violation: Violation<'static>,
+    val: &dyn fmt::Debug,
+    fmt: fmt::Arguments<'_>,
+) -> ! {
+    violated(violation, Some(format_args!("{val:?}: {fmt}")))
 }
 
-#[inline]
-fn unreachable_dbg_fmt(fmt: std::fmt::Arguments<'_>) -> ! {
-    unsafe { crate::unreachable_dbg_fmt(format_args!("{}: {}", ERR_STR, fmt)) }
+/// Same as [`unreachable_dbg_msg()`], but with the message first, like [`Result::expect()`] formats it.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_expect(violation: Violation<'static>, val: &dyn fmt::Debug, msg: &str) -> ! {
+    violated(violation, Some(format_args!("{msg}: {val:?}")))
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::{panic_location, panic_message};
     use {super::*, crate::unwrap_unchecked_dbg};
 
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     fn returns_a_result(val: i32) -> Result<u8, ()> {
         u8::try_from(val).map_err(|_| ())
     }
 
+    struct NotDebug;
+
     #[test]
     fn unwrap_unchecked_dbg_success() {
         let x: Result<i32, ()> = Ok(7);
-        assert_eq!(unsafe { x.unwrap_unchecked_dbg() }, 7)
+        assert_eq!(unsafe { x.unwrap_unchecked_dbg() }, 7);
+        assert_eq!(unsafe { x.unwrap_debug_unchecked_dbg() }, 7);
+
+        let x: Result<i32, NotDebug> = Ok(7);
+        assert_eq!(unsafe { x.unwrap_unchecked_dbg_msg("missing value") }, 7);
+
+        fn generic_unwrap<R: ResultExt<i32>>(x: R) -> i32 {
+            unsafe { x.unwrap_unchecked_dbg() }
+        }
+        assert_eq!(generic_unwrap(Ok::<_, NotDebug>(7)), 7)
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Result::unwrap()` on an `Err` value"]
     fn unwrap_unchecked_dbg_failure() {
@@ -78,31 +449,71 @@ mod tests {
         let _ = unsafe { x.unwrap_unchecked_dbg() };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "called `Result::unwrap()` on an `Err` value"]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }"]
+    fn unwrap_debug_unchecked_dbg_failure() {
+        let x = "x".parse::<i32>();
+        let _ = unsafe { x.unwrap_debug_unchecked_dbg() };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_debug_unchecked_dbg_failure_matches_std() {
+        let x = || "x".parse::<i32>();
+        assert_eq!(
+            panic_message(|| unsafe { x().unwrap_debug_unchecked_dbg() }),
+            panic_message(|| x().unwrap())
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unwrap_unchecked_dbg!(x()) }),
+            panic_message(|| x().unwrap())
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: \"not a number\": missing value (expected 7)"]
+    fn unwrap_debug_unchecked_dbg_fmt_failure() {
+        let x: Result<i32, &str> = Err("not a number");
+        let _ = unsafe {
+            x.unwrap_debug_unchecked_dbg_fmt(format_args!("missing value (expected {})", 7))
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: (): missing value"]
+    fn unwrap_debug_unchecked_dbg_msg_failure() {
+        let x: Result<i32, ()> = Err(());
+        let _ = unsafe { x.unwrap_debug_unchecked_dbg_msg("missing value") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: ()"]
     fn unwrap_unchecked_dbg_macro_failure() {
         unsafe { unwrap_unchecked_dbg!(returns_a_result(255), "this should succeed") };
         unsafe { unwrap_unchecked_dbg!(returns_a_result(-7)) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Result::unwrap()` on an `Err` value: missing value"]
     fn unwrap_unchecked_dbg_msg_failure() {
-        let x: Result<i32, ()> = Err(());
+        let x: Result<i32, NotDebug> = Err(NotDebug);
         let _ = unsafe { x.unwrap_unchecked_dbg_msg("missing value") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "called `Result::unwrap()` on an `Err` value: missing value"]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: (): missing value"]
     fn unwrap_unchecked_dbg_msg_macro_failure() {
         unsafe { unwrap_unchecked_dbg!(returns_a_result(255), "this should succeed") };
         unsafe { unwrap_unchecked_dbg!(returns_a_result(-7), "missing value") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "called `Result::unwrap()` on an `Err` value: missing value (expected 7)"]
     fn unwrap_unchecked_dbg_fmt_failure() {
@@ -111,11 +522,166 @@ mod tests {
             unsafe { x.unwrap_unchecked_dbg_fmt(format_args!("missing value (expected {})", 7)) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "called `Result::unwrap()` on an `Err` value: missing value (expected -7)"]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: (): missing value (expected -7)"]
     fn unwrap_unchecked_dbg_fmt_macro_failure() {
         unsafe { unwrap_unchecked_dbg!(returns_a_result(255), "this should succeed") };
         unsafe { unwrap_unchecked_dbg!(returns_a_result(-7), "missing value (expected {})", -7) };
     }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "something went wrong: ParseIntError { kind: InvalidDigit }"]
+    fn expect_unchecked_dbg_failure() {
+        let x = "x".parse::<i32>();
+        let _ = unsafe { x.expect_unchecked_dbg("something went wrong") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn expect_unchecked_dbg_failure_matches_std() {
+        let x = || "x".parse::<i32>();
+        assert_eq!(
+            panic_message(|| unsafe { x().expect_unchecked_dbg("something went wrong") }),
+            panic_message(|| x().expect("something went wrong"))
+        );
+    }
+
+    #[test]
+    fn unwrap_err_unchecked_dbg_success() {
+        let x: Result<(), i32> = Err(7);
+        assert_eq!(unsafe { x.unwrap_err_unchecked_dbg() }, 7);
+        assert_eq!(unsafe { x.unwrap_err_debug_unchecked_dbg() }, 7);
+        assert_eq!(
+            unsafe { x.expect_err_unchecked_dbg("expected an error") },
+            7
+        );
+        assert_eq!(
+            unsafe { unwrap_unchecked_dbg!(err: x, "expected an error") },
+            7
+        );
+
+        let x = || Err::<NotDebug, i32>(7);
+        assert_eq!(
+            unsafe { x().unwrap_err_unchecked_dbg_msg("expected an error") },
+            7
+        );
+        assert_eq!(unsafe { unwrap_unchecked_dbg!(err: x()) }, 7);
+
+        fn generic_unwrap_err<R: ResultExt<NotDebug, Error = i32>>(x: R) -> i32 {
+            unsafe { x.unwrap_err_unchecked_dbg() }
+        }
+        assert_eq!(generic_unwrap_err(x()), 7)
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_err_unchecked_dbg_failure() {
+        let x = || Ok::<_, i32>(NotDebug);
+        assert_eq!(
+            panic_message(|| unsafe { x().unwrap_err_unchecked_dbg() }),
+            "called `Result::unwrap_err()` on an `Ok` value"
+        );
+        assert_eq!(
+            panic_message(|| unsafe { x().unwrap_err_unchecked_dbg_msg("expected an error") }),
+            "called `Result::unwrap_err()` on an `Ok` value: expected an error"
+        );
+        assert_eq!(
+            panic_message(|| unsafe {
+                x().unwrap_err_unchecked_dbg_fmt(format_args!("expected an error (not {})", 7))
+            }),
+            "called `Result::unwrap_err()` on an `Ok` value: expected an error (not 7)"
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unwrap_unchecked_dbg!(err: x(), "expected an error") }),
+            "called `Result::unwrap_err()` on an `Ok` value: expected an error"
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap_err()` on an `Ok` value: 7"]
+    fn unwrap_err_debug_unchecked_dbg_failure() {
+        let x = "7".parse::<i32>();
+        let _ = unsafe { x.unwrap_err_debug_unchecked_dbg() };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_err_debug_unchecked_dbg_failure_matches_std() {
+        let x = || "7".parse::<i32>();
+        assert_eq!(
+            panic_message(|| unsafe { x().unwrap_err_debug_unchecked_dbg() }),
+            panic_message(|| x().unwrap_err())
+        );
+        assert_eq!(
+            panic_message(|| unsafe { unwrap_unchecked_dbg!(err: x()) }),
+            panic_message(|| x().unwrap_err())
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap_err()` on an `Ok` value: 255: expected an error"]
+    fn unwrap_err_unchecked_dbg_msg_macro_failure() {
+        unsafe { unwrap_unchecked_dbg!(err: returns_a_result(-7), "this should succeed") };
+        unsafe { unwrap_unchecked_dbg!(err: returns_a_result(255), "expected an error") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap_err()` on an `Ok` value: 255: expected an error (not 255)"]
+    fn unwrap_err_unchecked_dbg_fmt_macro_failure() {
+        unsafe { unwrap_unchecked_dbg!(err: returns_a_result(-7)) };
+        unsafe {
+            unwrap_unchecked_dbg!(err: returns_a_result(255), "expected an error (not {})", 255)
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "expected an error: 7"]
+    fn expect_err_unchecked_dbg_failure() {
+        let x = "7".parse::<i32>();
+        let _ = unsafe { x.expect_err_unchecked_dbg("expected an error") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn expect_err_unchecked_dbg_failure_matches_std() {
+        let x = || "7".parse::<i32>();
+        assert_eq!(
+            panic_message(|| unsafe { x().expect_err_unchecked_dbg("expected an error") }),
+            panic_message(|| x().expect_err("expected an error"))
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_unchecked_dbg_location() {
+        let x: Result<i32, ()> = Err(());
+
+        let location = panic_location(|| unsafe { x.unwrap_unchecked_dbg() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { x.unwrap_unchecked_dbg_msg("missing value") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { unwrap_unchecked_dbg!(x, "missing value {}", 7) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { x.expect_unchecked_dbg("missing value") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let x: Result<i32, ()> = Ok(7);
+
+        let location = panic_location(|| unsafe { unwrap_unchecked_dbg!(err: x) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { x.expect_err_unchecked_dbg("expected an error") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
 }
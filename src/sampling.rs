@@ -0,0 +1,182 @@
+use core::{
+    cell::Cell,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// The sample rate used until [`set_sample_rate()`] is called.
+///
+/// May be set at compile time via the `MINIUNCHECKED_SAMPLE_RATE` environment variable, `1000` by default.
+pub const DEFAULT_SAMPLE_RATE: u32 = match option_env!("MINIUNCHECKED_SAMPLE_RATE") {
+    Some(rate) => match u32::from_str_radix(rate, 10) {
+        Ok(rate) => rate,
+        Err(_) => panic!("`MINIUNCHECKED_SAMPLE_RATE` must be a non-negative integer"),
+    },
+    None => 1000,
+};
+
+static SAMPLE_RATE: AtomicU32 = AtomicU32::new(DEFAULT_SAMPLE_RATE);
+
+std::thread_local! {
+    /// Number of calls on this thread since the last sampled one.
+    static CALLS: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Sets the process-global sample rate `n` - if the debug checks are otherwise disabled
+/// (see [`CHECKS_ENABLED`](crate::CHECKS_ENABLED)), but not explicitly forced off
+/// (via the `never-check` feature or `--cfg miniunchecked_checks="off"`),
+/// `unreachable_dbg()` and friends, as well as all extension trait methods,
+/// perform the debug checks for 1 in `n` calls on each thread, and use the unchecked operations otherwise.
+///
+/// `1` checks all calls, `0` checks none.
+///
+/// Only available with the `sampling` feature. See also [`DEFAULT_SAMPLE_RATE`].
+pub fn set_sample_rate(n: u32) {
+    SAMPLE_RATE.store(n, Ordering::Relaxed);
+}
+
+/// Returns the current process-global sample rate. See [`set_sample_rate()`].
+pub fn sample_rate() -> u32 {
+    SAMPLE_RATE.load(Ordering::Relaxed)
+}
+
+/// Returns `true` if the current call should be checked.
+#[inline]
+pub(crate) fn sample() -> bool {
+    let rate = sample_rate();
+
+    if rate == 0 {
+        return false;
+    }
+
+    CALLS.with(|calls| {
+        let n = calls.get() + 1;
+        let sampled = n >= rate;
+        calls.set(if sampled { 0 } else { n });
+        sampled
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::sync::{Mutex, MutexGuard, PoisonError},
+    };
+
+    /// Serializes the tests which change the process-global sample rate.
+    static RATE_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Sets the sample rate and resets the counter of this thread, until the returned guard is dropped.
+    fn with_rate(rate: u32) -> impl Drop {
+        struct Guard(#[allow(dead_code)] MutexGuard<'static, ()>);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                SAMPLE_RATE.store(DEFAULT_SAMPLE_RATE, Ordering::Relaxed);
+            }
+        }
+
+        let guard = Guard(RATE_LOCK.lock().unwrap_or_else(PoisonError::into_inner));
+        CALLS.set(0);
+        SAMPLE_RATE.store(rate, Ordering::Relaxed);
+        guard
+    }
+
+    #[test]
+    fn sample() {
+        let sampled = |rate: u32| {
+            let _rate = with_rate(rate);
+            (0..12).filter(|_| super::sample()).count()
+        };
+
+        assert_eq!(sampled(0), 0);
+        assert_eq!(sampled(1), 12);
+        assert_eq!(sampled(3), 4);
+        assert_eq!(sampled(5), 2);
+        assert_eq!(sampled(13), 0);
+    }
+
+    /// With the checks otherwise disabled, checks that the entry point uses the unchecked operation (`valid`)
+    /// for the calls which are not sampled, and performs the checks (`invalid` panics with `message`) for the sampled ones.
+    #[cfg(all(
+        not(miniunchecked_checks_enabled),
+        not(feature = "never-check"),
+        not(miniunchecked_checks = "off"),
+        not(feature = "abort-on-violation")
+    ))]
+    fn check_sampled<R>(valid: impl Fn() -> R, invalid: impl FnOnce() -> R, message: &str) {
+        let _rate = with_rate(3);
+
+        valid();
+        valid();
+        assert_eq!(CALLS.get(), 2);
+
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(invalid))
+            .err()
+            .unwrap();
+        assert!(payload.downcast::<String>().unwrap().contains(message));
+        assert_eq!(CALLS.get(), 0);
+
+        // `0` checks none.
+        set_sample_rate(0);
+        valid();
+        assert_eq!(CALLS.get(), 0);
+    }
+
+    #[cfg(all(
+        not(miniunchecked_checks_enabled),
+        not(feature = "never-check"),
+        not(miniunchecked_checks = "off"),
+        not(feature = "abort-on-violation")
+    ))]
+    #[test]
+    fn sampled_entry_points() {
+        use crate::{OptionExt, ResultExt, SliceExt, StrExt};
+
+        let slice = [2, 3, 4];
+        check_sampled(
+            || unsafe { *slice.get_unchecked_dbg(2) },
+            || unsafe { *slice.get_unchecked_dbg(3) },
+            "index out of bounds: the len is 3 but the index is 3",
+        );
+        check_sampled(
+            || unsafe { slice.get_unchecked_dbg(1..3).len() },
+            || unsafe { slice.get_unchecked_dbg(1..4).len() },
+            "range end index 4 out of range for slice of length 3",
+        );
+
+        check_sampled(
+            || unsafe { [2, 3, 4].get_unchecked_mut_dbg(..=2).len() },
+            || unsafe { [2, 3, 4].get_unchecked_mut_dbg(..=3).len() },
+            "range end index 3 out of range for slice of length 3",
+        );
+
+        let string = "föo";
+        check_sampled(
+            || unsafe { string.get_unchecked_dbg(1..3).len() },
+            || unsafe { string.get_unchecked_dbg(2..3).len() },
+            "start byte index 2 is not a char boundary",
+        );
+
+        check_sampled(
+            || unsafe { Some(7).unwrap_unchecked_dbg() },
+            || unsafe { None::<i32>.unwrap_unchecked_dbg() },
+            "called `Option::unwrap()` on a `None` value",
+        );
+        check_sampled(
+            || unsafe { Ok::<i32, ()>(7).unwrap_unchecked_dbg() },
+            || unsafe { Err::<i32, ()>(()).unwrap_unchecked_dbg() },
+            "called `Result::unwrap()` on an `Err` value",
+        );
+    }
+
+    #[cfg(any(feature = "never-check", miniunchecked_checks = "off"))]
+    #[test]
+    fn forced_off() {
+        use crate::SliceExt;
+
+        let _rate = with_rate(1);
+        assert_eq!(unsafe { *[2, 3, 4].get_unchecked_dbg(2) }, 4);
+        assert_eq!(CALLS.get(), 0);
+    }
+}
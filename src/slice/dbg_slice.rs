@@ -0,0 +1,186 @@
+use {
+    crate::SliceIndexExt,
+    core::{
+        fmt,
+        ops::{Deref, DerefMut, Index, IndexMut},
+    },
+};
+
+/// A thin wrapper around a slice whose square brackets indexing operator uses
+/// [`get_unchecked_dbg()`](SliceIndexExt::get_unchecked_dbg) / [`get_unchecked_mut_dbg()`](SliceIndexExt::get_unchecked_mut_dbg),
+/// i.e. panics in debug configuration in case the index is invalid (like indexing a slice does),
+/// but doesn't check the index in release configuration.
+///
+/// Created (unsafely) with [`SliceExt::dbg()`](crate::SliceExt::dbg) / [`SliceExt::dbg_mut()`](crate::SliceExt::dbg_mut)
+/// or [`DbgSlice::new()`] / [`DbgSlice::new_mut()`]; dereferences to the wrapped slice.
+///
+/// # Warning
+///
+/// Indexing the wrapper is a *safe* operation, yet it is unchecked in release configuration,
+/// so an invalid index is undefined behavior. The caller of [`DbgSlice::new()`] / [`DbgSlice::new_mut()`] promises
+/// that *every* index the wrapper is ever indexed with is valid, including the indexing done by any code
+/// the wrapper (or a reference to it) is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`].
+/// Keep the wrapper local and short-lived, and never hand it to code which may compute its own indices.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::SliceExt;
+///
+/// let mut v = vec![2, 3, 4];
+///
+/// // SAFETY: all indices below are in bounds.
+/// let slice = unsafe { v.dbg_mut() };
+/// slice[0] = 5;
+/// assert_eq!(slice[0], 5);
+/// assert_eq!(slice[1..], [3, 4]);
+/// assert_eq!(slice.len(), 3);
+/// ```
+#[repr(transparent)]
+pub struct DbgSlice<T>([T]);
+
+impl<T> DbgSlice<T> {
+    /// Wraps the `slice`.
+    ///
+    /// # Safety
+    ///
+    /// Every index the returned wrapper is ever indexed with must be valid
+    /// (in bounds, see [`get_unchecked()`](core::slice::SliceIndex::get_unchecked) documentation),
+    /// otherwise the behavior is undefined in release configuration. This includes the indices used by any code
+    /// the wrapper is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`], even though indexing is safe there.
+    #[inline]
+    pub unsafe fn new(slice: &[T]) -> &Self {
+        // SAFETY: `DbgSlice<T>` is a `#[repr(transparent)]` wrapper around `[T]`.
+        unsafe { &*(slice as *const [T] as *const Self) }
+    }
+
+    /// Wraps the mutable `slice`.
+    ///
+    /// # Safety
+    ///
+    /// Every index the returned wrapper is ever indexed with must be valid
+    /// (in bounds, see [`get_unchecked_mut()`](core::slice::SliceIndex::get_unchecked_mut) documentation),
+    /// otherwise the behavior is undefined in release configuration. This includes the indices used by any code
+    /// the wrapper is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`], even though indexing is safe there.
+    #[inline]
+    pub unsafe fn new_mut(slice: &mut [T]) -> &mut Self {
+        // SAFETY: `DbgSlice<T>` is a `#[repr(transparent)]` wrapper around `[T]`.
+        unsafe { &mut *(slice as *mut [T] as *mut Self) }
+    }
+}
+
+impl<T> Deref for DbgSlice<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for DbgSlice<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, I> Index<I> for DbgSlice<T>
+where
+    I: SliceIndexExt<[T]>,
+{
+    type Output = I::Output;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: I) -> &Self::Output {
+        // SAFETY: the index is valid, as promised when the wrapper was created.
+        unsafe { index.get_unchecked_dbg(&self.0, None) }
+    }
+}
+
+impl<T, I> IndexMut<I> for DbgSlice<T>
+where
+    I: SliceIndexExt<[T]>,
+{
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        // SAFETY: the index is valid, as promised when the wrapper was created.
+        unsafe { index.get_unchecked_mut_dbg(&mut self.0, None) }
+    }
+}
+
+impl<T> fmt::Debug for DbgSlice<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::{panic_location, panic_message};
+    use {super::*, crate::SliceExt};
+
+    #[test]
+    fn index_success() {
+        let mut vec = vec![2, 3, 4];
+        let slice = unsafe { vec.dbg_mut() };
+
+        assert_eq!(slice[1], 3);
+        assert_eq!(slice[1..], [3, 4]);
+        assert_eq!(slice[..=1], [2, 3]);
+
+        slice[1] = 5;
+        slice[..2].reverse();
+        assert_eq!(slice.len(), 3);
+        assert_eq!(format!("{slice:?}"), "[5, 2, 4]");
+        assert_eq!(vec, [5, 2, 4]);
+
+        let slice = unsafe { DbgSlice::new(&vec[1..]) };
+        assert_eq!(slice[0], 2);
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
+    fn index_failure() {
+        let slice = unsafe { [2, 3, 4].dbg() };
+        let _ = slice[3];
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn index_failure_matches_std() {
+        let slice = &[2, 3, 4][..];
+        assert_eq!(
+            panic_message(|| unsafe { slice.dbg()[3] }),
+            panic_message(|| slice[3])
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "range start index 4 out of range for slice of length 3"]
+    fn index_mut_failure() {
+        let mut array = [2, 3, 4];
+        let slice = unsafe { array.dbg_mut() };
+        slice[4..].reverse();
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn index_location() {
+        let mut array = [2, 3, 4];
+
+        let location = panic_location(|| unsafe { array.dbg()[3] });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { array.dbg_mut()[3] = 5 });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+}
@@ -1,43 +1,74 @@
 use crate::*;
 
-impl<T> SliceIndexExt<[T]> for std::ops::Range<usize> {
+impl<T> SliceIndexExt<[T]> for core::ops::Range<usize> {
     type Output = [T];
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a [T],
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = self.clone();
-        slice
-            .get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, slice.len(), msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, slice: &[T], msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked(self) };
+        }
+
+        let (start, end) = (self.start, self.end);
+        match slice.get(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(start, end, slice.len(), msg),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut [T], msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked_mut(self) };
+        }
+
+        let (start, end) = (self.start, self.end);
+        let len = slice.len();
+        match slice.get_mut(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(start, end, len, msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut [T],
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = self.clone();
+    fn try_get<'a>(self, slice: &'a [T]) -> Result<&'a Self::Output, IndexError<'a>> {
+        let (start, end) = (self.start, self.end);
+        match slice.get(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(start..end, slice.len())),
+        }
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, slice: &'a mut [T]) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        let (start, end) = (self.start, self.end);
         let len = slice.len();
-        slice
-            .get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, len, msg) })
+        match slice.get_mut(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(start..end, len)),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let slice = [2, 3, 4];
 
-        let do_test = |idx: std::ops::Range<usize>, res: &[i32]| {
+        let do_test = |idx: core::ops::Range<usize>, res: &[i32]| {
             assert_eq!(unsafe { slice.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked(idx.clone()) }, res);
             assert_eq!(slice.get(idx.clone()), Some(res));
@@ -58,7 +89,7 @@ mod tests {
         assert!(slice.get(4..4).is_none());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_start() {
@@ -67,15 +98,17 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg(3..5) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_start_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[3..5];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(3..5) }),
+            panic_message(|| &slice[3..5])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_end() {
@@ -84,15 +117,17 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg(1..4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_end_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[1..4];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(1..4) }),
+            panic_message(|| &slice[1..4])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 5 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure_start() {
@@ -101,7 +136,7 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg_msg(3..5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure_end() {
@@ -114,7 +149,7 @@ mod tests {
     fn get_unchecked_mut_dbg_success() {
         let mut slice = [2, 3, 4];
 
-        let mut do_test = |idx: std::ops::Range<usize>, res: &mut [i32]| {
+        let mut do_test = |idx: core::ops::Range<usize>, res: &mut [i32]| {
             assert_eq!(unsafe { slice.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(slice.get_mut(idx.clone()), Some(res));
@@ -134,7 +169,7 @@ mod tests {
         assert!(slice.get_mut(4..4).is_none());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_start() {
@@ -143,15 +178,21 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_mut_dbg(3..5) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_start_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[3..5];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(3..5) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[3..5];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_end() {
@@ -160,15 +201,21 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_mut_dbg(1..4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_end_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[1..4];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(1..4) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[1..4];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 5 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_start() {
@@ -177,7 +224,7 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(3..5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_end() {
@@ -185,4 +232,33 @@ mod tests {
         assert!(slice.get_mut(1..4).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(1..4, "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: core::ops::Range<usize>, res: Result<&[i32], &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                slice.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                slice
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(slice.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1..3, Ok(&[3, 4]));
+        do_test(3..3, Ok(&[]));
+        do_test(
+            0..4,
+            Err("range end index 4 out of range for slice of length 3"),
+        );
+        do_test(2..1, Err("slice index starts at 2 but ends at 1"));
+    }
 }
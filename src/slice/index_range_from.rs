@@ -1,43 +1,74 @@
 use crate::*;
 
-impl<T> SliceIndexExt<[T]> for std::ops::RangeFrom<usize> {
+impl<T> SliceIndexExt<[T]> for core::ops::RangeFrom<usize> {
     type Output = [T];
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a [T],
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, slice: &[T], msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked(self) };
+        }
+
+        let range = self.start..slice.len();
+        match slice.get(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(range.start, range.end, slice.len(), msg),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut [T], msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked_mut(self) };
+        }
+
+        let range = self.start..slice.len();
+        let len = slice.len();
+        match slice.get_mut(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(range.start, range.end, len, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, slice: &'a [T]) -> Result<&'a Self::Output, IndexError<'a>> {
         let range = self.start..slice.len();
-        slice
-            .get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, slice.len(), msg) })
+        match slice.get(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(range, slice.len())),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut [T],
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
+    fn try_get_mut<'a>(self, slice: &'a mut [T]) -> Result<&'a mut Self::Output, IndexError<'a>> {
         let range = self.start..slice.len();
         let len = slice.len();
-        slice
-            .get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, len, msg) })
+        match slice.get_mut(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(range, len)),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let slice = [2, 3, 4];
 
-        let do_test = |idx: std::ops::RangeFrom<usize>, res: &[i32]| {
+        let do_test = |idx: core::ops::RangeFrom<usize>, res: &[i32]| {
             assert_eq!(unsafe { slice.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked(idx.clone()) }, res);
             assert_eq!(slice.get(idx.clone()), Some(res));
@@ -50,7 +81,7 @@ mod tests {
         do_test(3.., &[]);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range start index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure() {
@@ -59,15 +90,17 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg(4..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range start index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[4..];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(4..) }),
+            panic_message(|| &slice[4..])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range start index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure() {
@@ -80,7 +113,7 @@ mod tests {
     fn get_unchecked_mut_dbg_success() {
         let mut slice = [2, 3, 4];
 
-        let mut do_test = |idx: std::ops::RangeFrom<usize>, res: &mut [i32]| {
+        let mut do_test = |idx: core::ops::RangeFrom<usize>, res: &mut [i32]| {
             assert_eq!(unsafe { slice.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(slice.get_mut(idx.clone()), Some(res));
@@ -92,7 +125,7 @@ mod tests {
         do_test(3.., &mut []);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range start index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure() {
@@ -101,15 +134,21 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_mut_dbg(4..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range start index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[4..];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(4..) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[4..];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "range start index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure() {
@@ -117,4 +156,32 @@ mod tests {
         assert!(slice.get_mut(4..).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(4.., "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: core::ops::RangeFrom<usize>, res: Result<&[i32], &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                slice.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                slice
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(slice.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1.., Ok(&[3, 4]));
+        do_test(3.., Ok(&[]));
+        do_test(
+            4..,
+            Err("range start index 4 out of range for slice of length 3"),
+        );
+    }
 }
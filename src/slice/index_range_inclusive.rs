@@ -1,43 +1,70 @@
 use crate::*;
 
-impl<T> SliceIndexExt<[T]> for std::ops::RangeInclusive<usize> {
+impl<T> SliceIndexExt<[T]> for core::ops::RangeInclusive<usize> {
     type Output = [T];
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a [T],
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = range_inclusive_into_range(self.clone());
-        slice
-            .get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, slice.len(), msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, slice: &[T], msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked(self) };
+        }
+
+        match range_inclusive_into_range(&self).and_then(|range| slice.get(range)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(self, slice.len(), msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut [T],
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = range_inclusive_into_range(self.clone());
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut [T], msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked_mut(self) };
+        }
+
         let len = slice.len();
-        slice
-            .get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, len, msg) })
+        match range_inclusive_into_range(&self).and_then(|range| slice.get_mut(range)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(self, len, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, slice: &'a [T]) -> Result<&'a Self::Output, IndexError<'a>> {
+        match range_inclusive_into_range(&self).and_then(|range| slice.get(range)) {
+            Some(output) => Ok(output),
+            None => Err(range_inclusive_error(&self, slice.len())),
+        }
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, slice: &'a mut [T]) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        let len = slice.len();
+        match range_inclusive_into_range(&self).and_then(|range| slice.get_mut(range)) {
+            Some(output) => Ok(output),
+            None => Err(range_inclusive_error(&self, len)),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::{panic_location, panic_message};
 
     #[test]
     fn get_unchecked_dbg_success() {
         let slice = [2, 3, 4];
 
-        let do_test = |idx: std::ops::RangeInclusive<usize>, res: &[i32]| {
+        let do_test = |idx: core::ops::RangeInclusive<usize>, res: &[i32]| {
             assert_eq!(unsafe { slice.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked(idx.clone()) }, res);
             assert_eq!(slice.get(idx.clone()), Some(res));
@@ -52,68 +79,117 @@ mod tests {
         do_test(0..=2, &[2, 3, 4]);
     }
 
-    #[cfg(debug_assertions)]
+    /// Returns `range` after iterating it to the end.
+    fn exhausted(mut range: core::ops::RangeInclusive<usize>) -> core::ops::RangeInclusive<usize> {
+        range.by_ref().for_each(drop);
+        assert!(range.is_empty());
+        range
+    }
+
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
+    fn get_unchecked_dbg_exhausted_success() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: core::ops::RangeInclusive<usize>| {
+            assert_eq!(unsafe { slice.get_unchecked_dbg(idx.clone()) }, &[]);
+            assert_eq!(slice.get(idx.clone()), Some(&[][..]));
+            assert_eq!(unsafe { slice.get_unchecked_mut_dbg(idx.clone()) }, &mut []);
+            assert_eq!(slice.get_mut(idx.clone()), Some(&mut [][..]));
+        };
+
+        do_test(exhausted(0..=0));
+        do_test(exhausted(1..=2));
+        do_test(exhausted(2..=2));
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "range end index 3 out of range for slice of length 3"]
+    fn get_unchecked_dbg_exhausted_failure() {
+        let slice = [2, 3, 4];
+        let idx = exhausted(3..=3);
+        assert!(slice.get(idx.clone()).is_none());
+        let _ = unsafe { slice.get_unchecked_dbg(idx) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_exhausted_failure_matches_std() {
+        let slice = [2, 3, 4];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(exhausted(3..=3)) }),
+            panic_message(|| &slice[exhausted(3..=3)])
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "range end index 18446744073709551615 out of range for slice of length 3"]
     fn get_unchecked_dbg_usize_overflow() {
         let slice = [2, 3, 4];
         let _ = unsafe { slice.get_unchecked_dbg(0..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
     fn get_unchecked_dbg_usize_overflow_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[0..=usize::MAX];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(0..=usize::MAX) }),
+            panic_message(|| &slice[0..=usize::MAX])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
+    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_start() {
         let slice = [2, 3, 4];
         assert!(slice.get(3..=4).is_none());
         let _ = unsafe { slice.get_unchecked_dbg(3..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_start_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[3..=4];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(3..=4) }),
+            panic_message(|| &slice[3..=4])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
+    #[should_panic = "range end index 3 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_end() {
         let slice = [2, 3, 4];
         assert!(slice.get(1..=3).is_none());
         let _ = unsafe { slice.get_unchecked_dbg(1..=3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_end_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[1..=3];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(1..=3) }),
+            panic_message(|| &slice[1..=3])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure_start() {
         let slice = [2, 3, 4];
         assert!(slice.get(3..=4).is_none());
         let _ = unsafe { slice.get_unchecked_dbg_msg(3..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 3 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure_end() {
         let slice = [2, 3, 4];
         assert!(slice.get(1..=3).is_none());
@@ -124,7 +200,7 @@ mod tests {
     fn get_unchecked_mut_dbg_success() {
         let mut slice = [2, 3, 4];
 
-        let mut do_test = |idx: std::ops::RangeInclusive<usize>, res: &mut [i32]| {
+        let mut do_test = |idx: core::ops::RangeInclusive<usize>, res: &mut [i32]| {
             assert_eq!(unsafe { slice.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { slice.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(slice.get_mut(idx.clone()), Some(res));
@@ -138,71 +214,165 @@ mod tests {
         do_test(0..=2, &mut [2, 3, 4]);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
+    #[should_panic = "range end index 18446744073709551615 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_usize_overflow() {
         let mut slice = [2, 3, 4];
         let _ = unsafe { slice.get_unchecked_mut_dbg(0..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
     fn get_unchecked_mut_dbg_usize_overflow_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[0..=usize::MAX];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(0..=usize::MAX) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[0..=usize::MAX];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
+    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_start() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(3..=4).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg(3..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_start_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[3..=4];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(3..=4) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[3..=4];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
+    #[should_panic = "range end index 3 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_end() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(1..=3).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg(1..=3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_end_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[1..=3];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(1..=3) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[1..=3];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 5 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_start() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(3..=4).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(3..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 3 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_end() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(1..=3).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(1..=3, "invalid range") };
     }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_location() {
+        let mut slice = [2, 3, 4];
+
+        let location = panic_location(|| unsafe { slice.get_unchecked_dbg(1..=3) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { slice.get_unchecked_dbg(0..=usize::MAX) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { slice.get_unchecked_mut_dbg_msg(1..=3, "invalid range") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    #[test]
+    fn try_get() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: core::ops::RangeInclusive<usize>, res: Result<&[i32], &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                slice.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                slice
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(slice.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1..=2, Ok(&[3, 4]));
+        do_test(1..=0, Ok(&[]));
+        do_test(
+            0..=3,
+            Err("range end index 3 out of range for slice of length 3"),
+        );
+        do_test(3..=1, Err("slice index starts at 3 but ends at 2"));
+        do_test(
+            0..=usize::MAX,
+            Err("range end index 18446744073709551615 out of range for slice of length 3"),
+        );
+        do_test(exhausted(2..=2), Ok(&[]));
+        do_test(
+            exhausted(3..=3),
+            Err("range end index 3 out of range for slice of length 3"),
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn try_get_matches_std() {
+        let slice = [2, 3, 4];
+
+        let do_test = |idx: core::ops::RangeInclusive<usize>| {
+            assert_eq!(
+                slice.try_get(idx.clone()).unwrap_err().to_string(),
+                panic_message(|| &slice[idx])
+            );
+        };
+
+        do_test(1..=3);
+        do_test(3..=4);
+        do_test(5..=6);
+        do_test(3..=1);
+        do_test(0..=usize::MAX);
+        do_test(5..=usize::MAX);
+        do_test(exhausted(3..=3));
+        do_test(exhausted(4..=4));
+    }
 }
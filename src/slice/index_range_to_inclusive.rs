@@ -1,43 +1,63 @@
 use crate::*;
 
-impl<T> SliceIndexExt<[T]> for std::ops::RangeToInclusive<usize> {
+impl<T> SliceIndexExt<[T]> for core::ops::RangeToInclusive<usize> {
     type Output = [T];
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a [T],
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = range_inclusive_into_range(0..=self.end);
-        slice
-            .get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, slice.len(), msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, slice: &[T], msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked(self) };
+        }
+
+        match self.end.checked_add(1).and_then(|end| slice.get(..end)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(0..=self.end, slice.len(), msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut [T],
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = range_inclusive_into_range(0..=self.end);
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut [T], msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked_mut(self) };
+        }
+
         let len = slice.len();
-        slice
-            .get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(range, len, msg) })
+        match self.end.checked_add(1).and_then(|end| slice.get_mut(..end)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(0..=self.end, len, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, slice: &'a [T]) -> Result<&'a Self::Output, IndexError<'a>> {
+        (0..=self.end).try_get(slice)
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, slice: &'a mut [T]) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        (0..=self.end).try_get_mut(slice)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let slice = [2, 3, 4];
 
-        let do_test = |idx: std::ops::RangeToInclusive<usize>, res: &[i32]| {
+        let do_test = |idx: core::ops::RangeToInclusive<usize>, res: &[i32]| {
             assert_eq!(unsafe { slice.get_unchecked_dbg(idx) }, res);
             assert_eq!(unsafe { slice.get_unchecked(idx) }, res);
             assert_eq!(slice.get(idx), Some(res));
@@ -49,42 +69,46 @@ mod tests {
         do_test(..=2, &[2, 3, 4]);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
+    #[should_panic = "range end index 18446744073709551615 out of range for slice of length 3"]
     fn get_unchecked_dbg_usize_overflow() {
         let slice = [2, 3, 4];
         let _ = unsafe { slice.get_unchecked_dbg(..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
     fn get_unchecked_dbg_usize_overflow_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[..=usize::MAX];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(..=usize::MAX) }),
+            panic_message(|| &slice[..=usize::MAX])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
+    #[should_panic = "range end index 3 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure() {
         let slice = [2, 3, 4];
         assert!(slice.get(..=3).is_none());
         let _ = unsafe { slice.get_unchecked_dbg(..=3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_dbg_failure_matches_std() {
         let slice = [2, 3, 4];
-        let _ = &slice[..=3];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(..=3) }),
+            panic_message(|| &slice[..=3])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 3 out of range for slice of length 3: invalid range"]
     fn get_unchecked_dbg_msg_failure() {
         let slice = [2, 3, 4];
         assert!(slice.get(..=3).is_none());
@@ -95,7 +119,7 @@ mod tests {
     fn get_unchecked_mut_dbg_success() {
         let mut slice = [2, 3, 4];
 
-        let mut do_test = |idx: std::ops::RangeToInclusive<usize>, res: &mut [i32]| {
+        let mut do_test = |idx: core::ops::RangeToInclusive<usize>, res: &mut [i32]| {
             assert_eq!(unsafe { slice.get_unchecked_mut_dbg(idx) }, res);
             assert_eq!(unsafe { slice.get_unchecked_mut(idx) }, res);
             assert_eq!(slice.get_mut(idx), Some(res));
@@ -106,45 +130,88 @@ mod tests {
         do_test(..=2, &mut [2, 3, 4]);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
+    #[should_panic = "range end index 18446744073709551615 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_usize_overflow() {
         let mut slice = [2, 3, 4];
         let _ = unsafe { slice.get_unchecked_mut_dbg(..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index slice up to maximum usize"]
     fn get_unchecked_mut_dbg_usize_overflow_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[..=usize::MAX];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(..=usize::MAX) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[..=usize::MAX];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
+    #[should_panic = "range end index 3 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(..=3).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg(..=3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3"]
     fn get_unchecked_mut_dbg_failure_matches_std() {
         let mut slice = [2, 3, 4];
-        let _ = &mut slice[..=3];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(..=3) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[..=3];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "range end index 4 out of range for slice of length 3: invalid range"]
+    #[should_panic = "range end index 3 out of range for slice of length 3: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure() {
         let mut slice = [2, 3, 4];
         assert!(slice.get_mut(..=3).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(..=3, "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: core::ops::RangeToInclusive<usize>, res: Result<&[i32], &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                slice.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                slice
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(slice.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(..=1, Ok(&[2, 3]));
+        do_test(
+            ..=3,
+            Err("range end index 3 out of range for slice of length 3"),
+        );
+        do_test(
+            ..=usize::MAX,
+            Err("range end index 18446744073709551615 out of range for slice of length 3"),
+        );
+    }
 }
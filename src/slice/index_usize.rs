@@ -1,45 +1,81 @@
-use crate::*;
+use crate::{violation::violated_msg, *};
 
 impl<T> SliceIndexExt<[T]> for usize {
     type Output = T;
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a [T],
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        slice
-            .get(self)
-            .unwrap_or_else(|| unreachable_dbg_index(self, slice.len(), msg))
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, slice: &[T], msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked(self) };
+        }
+
+        match slice.get(self) {
+            Some(output) => output,
+            None => unreachable_dbg_index(self, slice.len(), msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut [T],
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut [T], msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { slice.get_unchecked_mut(self) };
+        }
+
         let len = slice.len();
-        slice
-            .get_mut(self)
-            .unwrap_or_else(|| unreachable_dbg_index(self, len, msg))
+        match slice.get_mut(self) {
+            Some(output) => output,
+            None => unreachable_dbg_index(self, len, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, slice: &'a [T]) -> Result<&'a Self::Output, IndexError<'a>> {
+        match slice.get(self) {
+            Some(output) => Ok(output),
+            None => Err(IndexError::new(Violation::IndexOutOfBounds {
+                index: self,
+                len: slice.len(),
+            })),
+        }
     }
-}
 
-fn unreachable_dbg_index(index: usize, len: usize, msg: Option<&'static str>) -> ! {
-    unsafe {
-        unreachable_dbg_fmt(format_args!(
-            "index out of bounds: the len is {len} but the index is {index}{}{}",
-            if msg.is_some() { ": " } else { "" },
-            if let Some(msg) = msg { msg } else { "" }
-        ))
+    #[inline]
+    fn try_get_mut<'a>(self, slice: &'a mut [T]) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        let len = slice.len();
+        match slice.get_mut(self) {
+            Some(output) => Ok(output),
+            None => Err(IndexError::new(Violation::IndexOutOfBounds {
+                index: self,
+                len,
+            })),
+        }
     }
 }
 
+/// Reports the out of bounds `index` into a slice of length `len`.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_index(index: usize, len: usize, msg: impl DbgMessage) -> ! {
+    violated_msg(Violation::IndexOutOfBounds { index, len }, msg)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::{
+        get_unchecked_dbg, get_unchecked_mut_dbg,
+        tests::{panic_location, panic_message},
+    };
 
     #[test]
     fn get_unchecked_dbg_success() {
@@ -57,7 +93,7 @@ mod tests {
         do_test(2, &4);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
     fn get_unchecked_dbg_failure() {
@@ -66,16 +102,18 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg(3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
+    #[allow(unconditional_panic)]
     fn get_unchecked_dbg_failure_matches_std() {
         let slice = [2, 3, 4];
-        #[allow(unconditional_panic)]
-        let _ = &slice[3];
+        assert_eq!(
+            panic_message(|| unsafe { slice.get_unchecked_dbg(3) }),
+            panic_message(|| &slice[3])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index"]
     fn get_unchecked_dbg_msg_failure() {
@@ -84,6 +122,65 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_dbg_msg(3, "invalid index") };
     }
 
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index 3"]
+    fn get_unchecked_dbg_fmt_failure() {
+        let slice = [2, 3, 4];
+        let _ = unsafe { slice.get_unchecked_dbg_fmt(3, format_args!("invalid index {}", 3)) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
+    fn get_unchecked_dbg_macro_failure() {
+        let slice: Vec<_> = (2..5).collect();
+        assert_eq!(unsafe { get_unchecked_dbg!(slice, 2) }, &4);
+        let _ = unsafe { get_unchecked_dbg!(slice, 3) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index"]
+    fn get_unchecked_dbg_msg_macro_failure() {
+        let slice = [2, 3, 4];
+        assert_eq!(unsafe { get_unchecked_dbg!(slice, 2, "invalid index") }, &4);
+        let _ = unsafe { get_unchecked_dbg!(slice, 3, "invalid index") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index 3"]
+    fn get_unchecked_dbg_fmt_macro_failure() {
+        let slice = &[2, 3, 4][..];
+        let index = 3;
+        assert_eq!(
+            unsafe { get_unchecked_dbg!(slice, 2, "invalid index {}", 2) },
+            &4
+        );
+        let _ = unsafe { get_unchecked_dbg!(slice, index, "invalid index {}", index) };
+    }
+
+    #[test]
+    fn get_unchecked_dbg_with_success() {
+        let mut slice = [2, 3, 4];
+        let unevaluated = || -> &str { panic!("the message must not be evaluated") };
+        assert_eq!(unsafe { slice.get_unchecked_dbg_with(1, unevaluated) }, &3);
+        assert_eq!(
+            unsafe { slice.get_unchecked_mut_dbg_with(1, unevaluated) },
+            &3
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index 3"]
+    fn get_unchecked_dbg_with_failure() {
+        let slice = [2, 3, 4];
+        let index = 3;
+        let _ = unsafe { slice.get_unchecked_dbg_with(index, || format!("invalid index {index}")) };
+    }
+
     #[test]
     fn get_unchecked_mut_dbg_success() {
         let mut slice = [2, 3, 4];
@@ -99,7 +196,7 @@ mod tests {
         do_test(2, &mut 4);
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
     fn get_unchecked_mut_dbg_failure() {
@@ -108,16 +205,22 @@ mod tests {
         let _ = unsafe { slice.get_unchecked_mut_dbg(3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
+    #[allow(unconditional_panic)]
     fn get_unchecked_mut_dbg_failure_matches_std() {
         let mut slice = [2, 3, 4];
-        #[allow(unconditional_panic)]
-        let _ = &mut slice[3];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { slice.get_unchecked_mut_dbg(3) };
+            }),
+            panic_message(|| {
+                let _ = &mut slice[3];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
     #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index"]
     fn slice_get_unchecked_mut_dbg_msg_failure() {
@@ -125,4 +228,88 @@ mod tests {
         assert!(slice.get_mut(3).is_none());
         let _ = unsafe { slice.get_unchecked_mut_dbg_msg(3, "invalid index") };
     }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index 3"]
+    fn get_unchecked_mut_dbg_fmt_failure() {
+        let mut slice = [2, 3, 4];
+        let _ = unsafe { slice.get_unchecked_mut_dbg_fmt(3, format_args!("invalid index {}", 3)) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: 3"]
+    fn get_unchecked_mut_dbg_with_failure() {
+        let mut slice = [2, 3, 4];
+        let index = 3;
+        let _ = unsafe { slice.get_unchecked_mut_dbg_with(index, || index) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3: invalid index 3"]
+    fn get_unchecked_mut_dbg_macro_failure() {
+        let mut slice = vec![2, 3, 4];
+        unsafe { *get_unchecked_mut_dbg!(slice, 0) = 5 };
+        unsafe { *get_unchecked_mut_dbg!(slice, 1, "invalid index") = 6 };
+        unsafe { *get_unchecked_mut_dbg!(slice, 2, "invalid index {}", 2) = 7 };
+        assert_eq!(slice, [5, 6, 7]);
+        let _ = unsafe { get_unchecked_mut_dbg!(slice, 3, "invalid index {}", 3) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_location() {
+        let mut slice = [2, 3, 4];
+
+        let location = panic_location(|| unsafe { slice.get_unchecked_dbg(3) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { slice.get_unchecked_dbg_msg(3, "invalid index") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { slice.get_unchecked_mut_dbg(3) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe {
+            slice.get_unchecked_dbg_fmt(3, format_args!("invalid index {}", 3))
+        });
+        assert_eq!(location, (file!().to_string(), line!() - 2));
+
+        let location = panic_location(|| unsafe { slice.get_unchecked_dbg_with(3, || 3) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { get_unchecked_dbg!(slice, 3, "invalid index") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    #[test]
+    fn try_get() {
+        let mut slice = [2, 3, 4];
+
+        let mut do_test = |idx: usize, res: Result<&i32, &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                slice.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                slice
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(slice.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(0, Ok(&2));
+        do_test(2, Ok(&4));
+        do_test(
+            3,
+            Err("index out of bounds: the len is 3 but the index is 3"),
+        );
+    }
 }
@@ -1,3 +1,4 @@
+mod dbg_slice;
 mod index_range;
 mod index_range_from;
 mod index_range_inclusive;
@@ -5,52 +6,64 @@ mod index_range_to;
 mod index_range_to_inclusive;
 mod index_usize;
 
-use crate::{unreachable_dbg_fmt, unreachable_dbg_msg};
+pub use dbg_slice::DbgSlice;
 
-/// An extension trait for [`SliceIndex`](std::slice::SliceIndex)
-/// which provides alternatives to [`get_unchecked()`](std::slice::SliceIndex::get_unchecked) / [`get_unchecked_mut()`](std::slice::SliceIndex::get_unchecked_mut)
+use crate::{DbgMessage, IndexError, Indexed, Violation, violation::violated_msg};
+
+/// An extension trait for [`SliceIndex`](core::slice::SliceIndex)
+/// which provides alternatives to [`get_unchecked()`](core::slice::SliceIndex::get_unchecked) / [`get_unchecked_mut()`](core::slice::SliceIndex::get_unchecked_mut)
 /// which panic in debug configuration in case the index is invalid, with an optional custom message.
 ///
-/// Implemented for slices - for [`usize`], for slices and strings - for [`std::ops::Range<usize>`], [`std::ops::RangeInclusive<usize>`],
-/// [`std::ops::RangeFrom<usize>`], [`std::ops::RangeTo<usize>`], [`std::ops::RangeToInclusive<usize>`].
+/// Also provides [`try_get()`](SliceIndexExt::try_get) / [`try_get_mut()`](SliceIndexExt::try_get_mut),
+/// which return the reason why the index is invalid instead of panicking.
+///
+/// Implemented for slices - for [`usize`], for slices and strings - for [`core::ops::Range<usize>`], [`core::ops::RangeInclusive<usize>`],
+/// [`core::ops::RangeFrom<usize>`], [`core::ops::RangeTo<usize>`], [`core::ops::RangeToInclusive<usize>`].
 pub trait SliceIndexExt<T>
 where
     T: ?Sized,
 {
     type Output: ?Sized;
 
-    /// Alternative to [`get_unchecked()`](std::slice::SliceIndex::get_unchecked)
+    /// Alternative to [`get_unchecked()`](core::slice::SliceIndex::get_unchecked)
     /// which panics in debug configuration in case the index is invalid, with an optional custom message.
     ///
+    /// The message is only evaluated if the index is invalid, see [`DbgMessage`].
+    ///
     /// # Safety
     ///
-    /// See [`get_unchecked()`](std::slice::SliceIndex::get_unchecked) documentation.
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        slice: &'a T,
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output;
+    /// See [`get_unchecked()`](core::slice::SliceIndex::get_unchecked) documentation.
+    unsafe fn get_unchecked_dbg<M>(self, slice: &T, msg: M) -> &Self::Output
+    where
+        M: DbgMessage;
 
-    /// Alternative to [`get_unchecked_mut()`](std::slice::SliceIndex::get_unchecked_mut)
+    /// Alternative to [`get_unchecked_mut()`](core::slice::SliceIndex::get_unchecked_mut)
     /// which panics in debug configuration in case the index is invalid, with an optional custom message.
     ///
+    /// The message is only evaluated if the index is invalid, see [`DbgMessage`].
     ///
     /// # Safety
     ///
-    /// See [`get_unchecked_mut()`](std::slice::SliceIndex::get_unchecked_mut) documentation.
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        slice: &'a mut T,
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output;
+    /// See [`get_unchecked_mut()`](core::slice::SliceIndex::get_unchecked_mut) documentation.
+    unsafe fn get_unchecked_mut_dbg<M>(self, slice: &mut T, msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage;
+
+    /// Alternative to [`get()`](core::slice::SliceIndex::get)
+    /// which returns the reason why the index is invalid, if it is.
+    fn try_get<'a>(self, slice: &'a T) -> Result<&'a Self::Output, IndexError<'a>>;
+
+    /// Alternative to [`get_mut()`](core::slice::SliceIndex::get_mut)
+    /// which returns the reason why the index is invalid, if it is.
+    fn try_get_mut<'a>(self, slice: &'a mut T) -> Result<&'a mut Self::Output, IndexError<'a>>;
 }
 
 /// An extension trait for [`slice`](https://doc.rust-lang.org/std/primitive.slice.html)
 /// which provides alternatives to [`get_unchecked()`] / [`get_unchecked_mut()`]
 /// which panic in debug configuration in case the index is invalid, with an optional custom message.
 ///
-/// Implemented for [`usize`], [`std::ops::Range<usize>`], [`std::ops::RangeInclusive<usize>`],
-/// [`std::ops::RangeFrom<usize>`], [`std::ops::RangeTo<usize>`], [`std::ops::RangeToInclusive<usize>`].
+/// Implemented for [`usize`], [`core::ops::Range<usize>`], [`core::ops::RangeInclusive<usize>`],
+/// [`core::ops::RangeFrom<usize>`], [`core::ops::RangeTo<usize>`], [`core::ops::RangeToInclusive<usize>`].
 ///
 /// [`get_unchecked()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked
 /// [`get_unchecked_mut()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked_mut
@@ -109,19 +122,121 @@ pub trait SliceExt<T> {
     ) -> &mut <I as SliceIndexExt<[T]>>::Output
     where
         I: SliceIndexExt<[T]>;
+
+    /// Alternative to [`get_unchecked()`] which panics in debug configuration in case the index is invalid, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked()`] documentation.
+    ///
+    /// [`get_unchecked()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked
+    unsafe fn get_unchecked_dbg_fmt<I>(
+        &self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &<I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>;
+
+    /// Alternative to [`get_unchecked_mut()`] which panics in debug configuration in case the index is invalid, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked_mut()`] documentation.
+    ///
+    /// [`get_unchecked_mut()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked_mut
+    unsafe fn get_unchecked_mut_dbg_fmt<I>(
+        &mut self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &mut <I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>;
+
+    /// Alternative to [`get_unchecked()`] which panics in debug configuration in case the index is invalid,
+    /// with a custom error message returned by `f`, which is only called if the index is invalid.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked()`] documentation.
+    ///
+    /// [`get_unchecked()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked
+    unsafe fn get_unchecked_dbg_with<I, F, D>(
+        &self,
+        index: I,
+        f: F,
+    ) -> &<I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display;
+
+    /// Alternative to [`get_unchecked_mut()`] which panics in debug configuration in case the index is invalid,
+    /// with a custom error message returned by `f`, which is only called if the index is invalid.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked_mut()`] documentation.
+    ///
+    /// [`get_unchecked_mut()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked_mut
+    unsafe fn get_unchecked_mut_dbg_with<I, F, D>(
+        &mut self,
+        index: I,
+        f: F,
+    ) -> &mut <I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display;
+
+    /// Alternative to [`get()`] which returns the reason why the index is invalid, if it is.
+    ///
+    /// [`get()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get
+    fn try_get<I>(&self, index: I) -> Result<&<I as SliceIndexExt<[T]>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<[T]>;
+
+    /// Alternative to [`get_mut()`] which returns the reason why the index is invalid, if it is.
+    ///
+    /// [`get_mut()`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get_mut
+    fn try_get_mut<I>(
+        &mut self,
+        index: I,
+    ) -> Result<&mut <I as SliceIndexExt<[T]>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<[T]>;
+
+    /// Wraps the slice in a [`DbgSlice`], whose square brackets indexing operator panics in debug configuration
+    /// in case the index is invalid, but doesn't check the index in release configuration.
+    ///
+    /// # Safety
+    ///
+    /// Every index the wrapper is ever indexed with (also by any code it is passed to) must be valid,
+    /// see [`DbgSlice::new()`] documentation.
+    unsafe fn dbg(&self) -> &DbgSlice<T>;
+
+    /// Same as [`dbg()`](SliceExt::dbg), but wraps the mutable slice.
+    ///
+    /// # Safety
+    ///
+    /// Every index the wrapper is ever indexed with (also by any code it is passed to) must be valid,
+    /// see [`DbgSlice::new_mut()`] documentation.
+    unsafe fn dbg_mut(&mut self) -> &mut DbgSlice<T>;
 }
 
 impl<T> SliceExt<T> for [T] {
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_dbg<I>(&self, index: I) -> &<I as SliceIndexExt<[T]>>::Output
     where
         I: SliceIndexExt<[T]>,
     {
         // See `[T]::get_unchecked()`
-        &*unsafe { index.get_unchecked_dbg(self, None) }
+        unsafe { index.get_unchecked_dbg(self, None) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_mut_dbg<I>(
         &mut self,
         index: I,
@@ -130,10 +245,11 @@ impl<T> SliceExt<T> for [T] {
         I: SliceIndexExt<[T]>,
     {
         // See `[T]::get_unchecked_mut()`
-        &mut *unsafe { index.get_unchecked_mut_dbg(self, None) }
+        unsafe { index.get_unchecked_mut_dbg(self, None) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_dbg_msg<I>(
         &self,
         index: I,
@@ -143,10 +259,11 @@ impl<T> SliceExt<T> for [T] {
         I: SliceIndexExt<[T]>,
     {
         // See `[T]::get_unchecked()`
-        &*unsafe { index.get_unchecked_dbg(self, Some(msg)) }
+        unsafe { index.get_unchecked_dbg(self, Some(msg)) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_mut_dbg_msg<I>(
         &mut self,
         index: I,
@@ -156,51 +273,220 @@ impl<T> SliceExt<T> for [T] {
         I: SliceIndexExt<[T]>,
     {
         // See `[T]::get_unchecked_mut()`
-        &mut *unsafe { index.get_unchecked_mut_dbg(self, Some(msg)) }
+        unsafe { index.get_unchecked_mut_dbg(self, Some(msg)) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_dbg_fmt<I>(
+        &self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &<I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+    {
+        // See `[T]::get_unchecked()`
+        unsafe { index.get_unchecked_dbg(self, fmt) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg_fmt<I>(
+        &mut self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &mut <I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+    {
+        // See `[T]::get_unchecked_mut()`
+        unsafe { index.get_unchecked_mut_dbg(self, fmt) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_dbg_with<I, F, D>(
+        &self,
+        index: I,
+        f: F,
+    ) -> &<I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display,
+    {
+        // See `[T]::get_unchecked()`
+        unsafe { index.get_unchecked_dbg(self, f) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg_with<I, F, D>(
+        &mut self,
+        index: I,
+        f: F,
+    ) -> &mut <I as SliceIndexExt<[T]>>::Output
+    where
+        I: SliceIndexExt<[T]>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display,
+    {
+        // See `[T]::get_unchecked_mut()`
+        unsafe { index.get_unchecked_mut_dbg(self, f) }
+    }
+
+    #[inline]
+    fn try_get<I>(&self, index: I) -> Result<&<I as SliceIndexExt<[T]>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<[T]>,
+    {
+        index.try_get(self)
+    }
+
+    #[inline]
+    fn try_get_mut<I>(
+        &mut self,
+        index: I,
+    ) -> Result<&mut <I as SliceIndexExt<[T]>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<[T]>,
+    {
+        index.try_get_mut(self)
+    }
+
+    #[inline]
+    unsafe fn dbg(&self) -> &DbgSlice<T> {
+        // See `DbgSlice::new()`
+        unsafe { DbgSlice::new(self) }
+    }
+
+    #[inline]
+    unsafe fn dbg_mut(&mut self) -> &mut DbgSlice<T> {
+        // See `DbgSlice::new_mut()`
+        unsafe { DbgSlice::new_mut(self) }
     }
 }
 
-#[inline]
-pub(super) unsafe fn unreachable_dbg_range(
-    range: std::ops::Range<usize>,
+/// Reports the invalid `start..end` range into a slice of length `len`.
+///
+/// This and all other failure paths are cold and out of line, and only take plain integers / pointers
+/// (and the not yet evaluated message), so that only the check is inlined at each call site.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(super) fn unreachable_dbg_range(
+    start: usize,
+    end: usize,
     len: usize,
-    msg: Option<&'static str>,
+    msg: impl DbgMessage,
 ) -> ! {
-    if range.end > len {
-        unsafe {
-            unreachable_dbg_fmt(format_args!(
-                "range end index {} out of range for slice of length {len}{}{}",
-                range.end,
-                if msg.is_some() { ": " } else { "" },
-                if let Some(msg) = msg { msg } else { "" }
-            ))
+    violated_msg(range_violation(start..end, len), msg)
+}
+
+/// Returns the reason why `range` is not a valid index into a slice of length `len`.
+///
+/// `range` must be invalid.
+pub(super) fn range_violation(range: core::ops::Range<usize>, len: usize) -> Violation<'static> {
+    let indexed = Indexed::Slice { len };
+
+    if range.start > len {
+        Violation::RangeStartOutOfBounds {
+            start: range.start,
+            indexed,
+        }
+    } else if range.end > len {
+        Violation::RangeEndOutOfBounds {
+            end: range.end,
+            indexed,
         }
     } else {
-        debug_assert!(range.start > len);
-
-        unsafe {
-            unreachable_dbg_fmt(format_args!(
-                "range start index {} out of range for slice of length {len}{}{}",
-                range.start,
-                if msg.is_some() { ": " } else { "" },
-                if let Some(msg) = msg { msg } else { "" }
-            ))
+        debug_assert!(range.start > range.end);
+
+        Violation::RangeInverted {
+            start: range.start,
+            end: range.end,
+            indexed,
         }
     }
 }
 
-/// Based on `slice::index::slice_end_index_overflow_fail`.
-fn slice_end_index_overflow_fail() -> ! {
-    unsafe { unreachable_dbg_msg("attempted to index slice up to maximum usize") };
+/// Returns the error for the invalid `range` into a slice of length `len`.
+pub(super) fn range_error(range: core::ops::Range<usize>, len: usize) -> IndexError<'static> {
+    IndexError::new(range_violation(range, len))
 }
 
-/// Based on `std::ops::RangeInclusive<usize>::into_slice_range`, but does not handle exhausted ranges.
-/// TODO: revisit exhausted handling.
-pub(super) fn range_inclusive_into_range(
-    range: std::ops::RangeInclusive<usize>,
-) -> std::ops::Range<usize> {
-    if *range.end() == usize::MAX {
-        slice_end_index_overflow_fail();
+/// Reports the invalid inclusive `range` into a slice of length `len`.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(super) fn unreachable_dbg_range_inclusive(
+    range: core::ops::RangeInclusive<usize>,
+    len: usize,
+    msg: impl DbgMessage,
+) -> ! {
+    violated_msg(range_inclusive_violation(&range, len), msg)
+}
+
+/// Returns the reason why the inclusive `range` is not a valid index into a slice of length `len`.
+///
+/// `range` must be invalid.
+pub(super) fn range_inclusive_violation(
+    range: &core::ops::RangeInclusive<usize>,
+    len: usize,
+) -> Violation<'static> {
+    let indexed = Indexed::Slice { len };
+
+    // Like the standard library, reports the inclusive end rather than the exclusive one it converts it to,
+    // also for an exhausted range.
+    if *range.start() > len {
+        Violation::RangeStartOutOfBounds {
+            start: *range.start(),
+            indexed,
+        }
+    } else if *range.end() == usize::MAX {
+        Violation::IndexOverflow { indexed }
+    } else if *range.end() >= len {
+        Violation::RangeEndOutOfBounds {
+            end: *range.end(),
+            indexed,
+        }
+    } else {
+        // The range can only be inverted here, which the exclusive range reports.
+        match range_inclusive_into_range(range) {
+            Some(range) => range_violation(range, len),
+            None => Violation::IndexOverflow { indexed },
+        }
     }
-    *range.start()..*range.end() + 1
+}
+
+/// Returns the error for the invalid inclusive `range` into a slice of length `len`.
+pub(super) fn range_inclusive_error(
+    range: &core::ops::RangeInclusive<usize>,
+    len: usize,
+) -> IndexError<'static> {
+    IndexError::new(range_inclusive_violation(range, len))
+}
+
+/// Converts the inclusive `range` into the equivalent exclusive one, like the standard library does
+/// before indexing with it (so the failure paths only need the plain integer bounds).
+///
+/// Based on `core::ops::RangeInclusive<usize>::into_slice_range`.
+///
+/// Returns `None` if `range` ends at [`usize::MAX`], which is always an invalid index.
+#[inline]
+pub(crate) fn range_inclusive_into_range(
+    range: &core::ops::RangeInclusive<usize>,
+) -> Option<core::ops::Range<usize>> {
+    let exclusive_end = range.end().checked_add(1)?;
+    // If the range is exhausted (e.g. by iterating it), slicing with `end + 1..end + 1` gives an empty range
+    // which is still subject to bounds checks for that endpoint.
+    // The exhausted flag is private, but an exhausted range is the only empty one with `start <= end`.
+    let exhausted = range.is_empty() && range.start() <= range.end();
+    let start = if exhausted {
+        exclusive_end
+    } else {
+        *range.start()
+    };
+    Some(start..exclusive_end)
 }
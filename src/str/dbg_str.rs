@@ -0,0 +1,187 @@
+use {
+    crate::SliceIndexExt,
+    core::{
+        fmt,
+        ops::{Deref, DerefMut, Index, IndexMut},
+    },
+};
+
+/// A thin wrapper around a string slice whose square brackets indexing operator uses
+/// [`get_unchecked_dbg()`](SliceIndexExt::get_unchecked_dbg) / [`get_unchecked_mut_dbg()`](SliceIndexExt::get_unchecked_mut_dbg),
+/// i.e. panics in debug configuration in case the index is invalid (like indexing a string does),
+/// but doesn't check the index in release configuration.
+///
+/// Created (unsafely) with [`StrExt::dbg()`](crate::StrExt::dbg) / [`StrExt::dbg_mut()`](crate::StrExt::dbg_mut)
+/// or [`DbgStr::new()`] / [`DbgStr::new_mut()`]; dereferences to the wrapped string slice.
+///
+/// # Warning
+///
+/// Indexing the wrapper is a *safe* operation, yet it is unchecked in release configuration,
+/// so an invalid index is undefined behavior. The caller of [`DbgStr::new()`] / [`DbgStr::new_mut()`] promises
+/// that *every* index the wrapper is ever indexed with is valid, including the indexing done by any code
+/// the wrapper (or a reference to it) is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`].
+/// Keep the wrapper local and short-lived, and never hand it to code which may compute its own indices.
+///
+/// # Examples
+///
+/// ```
+/// use miniunchecked::StrExt;
+///
+/// let s = String::from("föo");
+///
+/// // SAFETY: all ranges below are in bounds and on char boundaries.
+/// let s = unsafe { s.dbg() };
+/// assert_eq!(&s[1..3], "ö");
+/// assert_eq!(&s[3..], "o");
+/// assert_eq!(s.len(), 4);
+/// ```
+#[repr(transparent)]
+pub struct DbgStr(str);
+
+impl DbgStr {
+    /// Wraps the string slice `s`.
+    ///
+    /// # Safety
+    ///
+    /// Every index the returned wrapper is ever indexed with must be valid
+    /// (in bounds and on char boundaries, see [`get_unchecked()`](str::get_unchecked) documentation),
+    /// otherwise the behavior is undefined in release configuration. This includes the indices used by any code
+    /// the wrapper is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`], even though indexing is safe there.
+    #[inline]
+    pub unsafe fn new(s: &str) -> &Self {
+        // SAFETY: `DbgStr` is a `#[repr(transparent)]` wrapper around `str`.
+        unsafe { &*(s as *const str as *const Self) }
+    }
+
+    /// Wraps the mutable string slice `s`.
+    ///
+    /// # Safety
+    ///
+    /// Every index the returned wrapper is ever indexed with must be valid
+    /// (in bounds and on char boundaries, see [`get_unchecked_mut()`](str::get_unchecked_mut) documentation),
+    /// otherwise the behavior is undefined in release configuration. This includes the indices used by any code
+    /// the wrapper is passed to, e.g. generic code bounded by [`Index`] / [`IndexMut`], even though indexing is safe there.
+    #[inline]
+    pub unsafe fn new_mut(s: &mut str) -> &mut Self {
+        // SAFETY: `DbgStr` is a `#[repr(transparent)]` wrapper around `str`.
+        unsafe { &mut *(s as *mut str as *mut Self) }
+    }
+}
+
+impl Deref for DbgStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for DbgStr {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<I> Index<I> for DbgStr
+where
+    I: SliceIndexExt<str>,
+{
+    type Output = I::Output;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: I) -> &Self::Output {
+        // SAFETY: the index is valid, as promised when the wrapper was created.
+        unsafe { index.get_unchecked_dbg(&self.0, None) }
+    }
+}
+
+impl<I> IndexMut<I> for DbgStr
+where
+    I: SliceIndexExt<str>,
+{
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        // SAFETY: the index is valid, as promised when the wrapper was created.
+        unsafe { index.get_unchecked_mut_dbg(&mut self.0, None) }
+    }
+}
+
+impl fmt::Debug for DbgStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for DbgStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::{panic_location, panic_message};
+    use {super::*, crate::StrExt};
+
+    #[test]
+    fn index_success() {
+        let mut string = "föo".to_string();
+        let s = unsafe { string.dbg_mut() };
+
+        assert_eq!(&s[1..3], "ö");
+        assert_eq!(&s[..=2], "fö");
+        assert_eq!(&s[3..], "o");
+
+        s[3..].make_ascii_uppercase();
+        assert_eq!(s.len(), 4);
+        assert_eq!(format!("{s} {s:?}"), "föO \"föO\"");
+        assert_eq!(string, "föO");
+
+        let s = unsafe { DbgStr::new(&string[1..]) };
+        assert_eq!(&s[..2], "ö");
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    fn index_failure() {
+        let s = unsafe { "föo".dbg() };
+        let _ = &s[2..];
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn index_failure_matches_std() {
+        let s = "föo";
+        assert_eq!(
+            panic_message(|| unsafe { s.dbg()[2..].len() }),
+            panic_message(|| s[2..].len())
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "end byte index 5 is out of bounds of `föo`"]
+    fn index_mut_failure() {
+        let mut string = "föo".to_string();
+        let s = unsafe { string.dbg_mut() };
+        s[..5].make_ascii_uppercase();
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn index_location() {
+        let mut string = "föo".to_string();
+
+        let location = panic_location(|| unsafe { string.dbg()[2..].len() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { string.dbg_mut()[..5].make_ascii_uppercase() });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+}
@@ -1,41 +1,77 @@
 use super::*;
 
-impl SliceIndexExt<str> for std::ops::Range<usize> {
+impl SliceIndexExt<str> for core::ops::Range<usize> {
     type Output = str;
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        s: &'a str,
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = self.clone();
-        s.get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(s, range, msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, s: &str, msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked(self) };
+        }
+
+        let (start, end) = (self.start, self.end);
+        match s.get(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(s, start, end, msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        s: &'a mut str,
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = self.clone();
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, s: &mut str, msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked_mut(self) };
+        }
+
+        let (start, end) = (self.start, self.end);
         let _s: *const str = &*s;
-        s.get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(&*_s, range, msg) })
+        match s.get_mut(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(unsafe { &*_s }, start, end, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, s: &'a str) -> Result<&'a Self::Output, IndexError<'a>> {
+        let (start, end) = (self.start, self.end);
+        match s.get(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(s, start..end)),
+        }
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, s: &'a mut str) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        let (start, end) = (self.start, self.end);
+        if s.get(start..end).is_none() {
+            return Err(range_error(s, start..end));
+        }
+        // SAFETY: the index was checked above.
+        Ok(unsafe { s.get_unchecked_mut(self) })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::{
+        get_unchecked_dbg, get_unchecked_mut_dbg,
+        tests::{panic_location, panic_message},
+    };
 
     #[test]
     fn get_unchecked_dbg_success() {
         let string = "föo";
 
-        let do_test = |idx: std::ops::Range<usize>, res: &str| {
+        let do_test = |idx: core::ops::Range<usize>, res: &str| {
             assert_eq!(unsafe { string.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked(idx.clone()) }, res);
             assert_eq!(string.get(idx.clone()), Some(res));
@@ -57,104 +93,174 @@ mod tests {
         assert!(string.get(5..5).is_none());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_start() {
         let string = "föo";
         assert!(string.get(5..6).is_none());
         let _ = unsafe { string.get_unchecked_dbg(5..6) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_start_matches_std() {
         let string = "föo";
-        let _ = &string[5..6];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(5..6) }),
+            panic_message(|| &string[5..6])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_end() {
         let string = "föo";
         assert!(string.get(0..5).is_none());
         let _ = unsafe { string.get_unchecked_dbg(0..5) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_end_matches_std() {
         let string = "föo";
-        let _ = &string[0..5];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(0..5) }),
+            panic_message(|| &string[0..5])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`"]
+    #[should_panic = "begin > end (1 > 0) when slicing `föo`"]
     fn get_unchecked_dbg_failure_begin_greater_than_end() {
         let string = "föo";
         assert!(string.get(1..0).is_none());
         let _ = unsafe { string.get_unchecked_dbg(1..0) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`"]
     fn get_unchecked_dbg_failure_begin_greater_than_end_matches_std() {
         let string = "föo";
-        let _ = &string[1..0];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(1..0) }),
+            panic_message(|| &string[1..0])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..3).is_none());
         let _ = unsafe { string.get_unchecked_dbg(2..3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary_matches_std() {
         let string = "föo";
-        let _ = &string[2..3];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(2..3) }),
+            panic_message(|| &string[2..3])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob_start() {
         let string = "föo";
         assert!(string.get(5..6).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(5..6, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range 2..3"]
+    fn get_unchecked_dbg_fmt_failure() {
+        let string = "föo";
+        let _ =
+            unsafe { string.get_unchecked_dbg_fmt(2..3, format_args!("invalid range {:?}", 2..3)) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range 5..6"]
+    fn get_unchecked_dbg_macro_failure() {
+        let string = "föo".to_string();
+        assert_eq!(unsafe { get_unchecked_dbg!(string, 1..3) }, "ö");
+        assert_eq!(
+            unsafe { get_unchecked_dbg!(string, 1..3, "invalid range") },
+            "ö"
+        );
+        let _ = unsafe { get_unchecked_dbg!(string, 5..6, "invalid range {:?}", 5..6) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föO`: invalid range"]
+    fn get_unchecked_mut_dbg_macro_failure() {
+        let mut string = "föo".to_string();
+        unsafe { get_unchecked_mut_dbg!(string, 1..3).make_ascii_uppercase() };
+        assert_eq!(string, "föo");
+        unsafe {
+            get_unchecked_mut_dbg!(string, 3..4, "invalid range {}", 3).make_ascii_uppercase()
+        };
+        assert_eq!(string, "föO");
+        let _ = unsafe { get_unchecked_mut_dbg!(string, 2..3, "invalid range") };
+    }
+
+    #[test]
+    fn get_unchecked_dbg_with_success() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+        let unevaluated = || -> &str { panic!("the message must not be evaluated") };
+        assert_eq!(
+            unsafe { string.get_unchecked_dbg_with(1..3, unevaluated) },
+            "ö"
+        );
+        assert_eq!(
+            unsafe { string.get_unchecked_mut_dbg_with(1..3, unevaluated) },
+            "ö"
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range 2..3"]
+    fn get_unchecked_dbg_with_failure() {
+        let string = "föo";
+        let range = 2..3;
+        let _ = unsafe {
+            string.get_unchecked_dbg_with(range.clone(), || format!("invalid range {range:?}"))
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "end byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob_end() {
         let string = "föo";
         assert!(string.get(0..5).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(0..5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`: invalid range"]
+    #[should_panic = "begin > end (1 > 0) when slicing `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_begin_greater_than_end() {
         let string = "föo";
         assert!(string.get(1..0).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(1..0, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..3).is_none());
@@ -166,7 +272,7 @@ mod tests {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
 
-        let mut do_test = |idx: std::ops::Range<usize>, res: &mut str| {
+        let mut do_test = |idx: core::ops::Range<usize>, res: &mut str| {
             assert_eq!(unsafe { string.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(string.get_mut(idx.clone()), Some(&mut *res));
@@ -188,9 +294,9 @@ mod tests {
         assert!(string.get_mut(5..5).is_none());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -198,18 +304,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(5..6) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[5..6];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(5..6) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[5..6];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -217,18 +329,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(0..5) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_end_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[0..5];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(0..5) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[0..5];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`"]
+    #[should_panic = "begin > end (1 > 0) when slicing `föo`"]
     fn get_unchecked_mut_dbg_failure_begin_greater_than_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -236,18 +354,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(1..0) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`"]
     fn get_unchecked_mut_dbg_failure_begin_greater_than_end_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[1..0];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(1..0) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[1..0];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -255,18 +379,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(2..3) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[2..3];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(2..3) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[2..3];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob_start() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -274,9 +404,32 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(5..6, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range 5..6"]
+    fn get_unchecked_mut_dbg_fmt_failure() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+        let _ = unsafe {
+            string.get_unchecked_mut_dbg_fmt(5..6, format_args!("invalid range {:?}", 5..6))
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range 5..6"]
+    fn get_unchecked_mut_dbg_with_failure() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+        let range = 5..6;
+        let _ = unsafe {
+            string.get_unchecked_mut_dbg_with(range.clone(), || format!("invalid range {range:?}"))
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "end byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -284,9 +437,9 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(0..5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (1 <= 0) when slicing `föo`: invalid range"]
+    #[should_panic = "begin > end (1 > 0) when slicing `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_begin_greater_than_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -294,13 +447,60 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(1..0, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
         assert!(string.get_mut(2..3).is_none());
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(2..3, "invalid range") };
     }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_location() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+
+        let location = panic_location(|| unsafe { string.get_unchecked_dbg(0..5) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location = panic_location(|| unsafe { string.get_unchecked_dbg(2..3) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { string.get_unchecked_mut_dbg_msg(1..0, "invalid range") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+
+    #[test]
+    fn try_get() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+
+        let mut do_test = |idx: core::ops::Range<usize>, res: Result<&str, &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                string.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                string
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(string.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1..3, Ok("ö"));
+        do_test(0..5, Err("end byte index 5 is out of bounds of `föo`"));
+        do_test(2..1, Err("begin > end (2 > 1) when slicing `föo`"));
+        do_test(
+            0..2,
+            Err("end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"),
+        );
+    }
 }
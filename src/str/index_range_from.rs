@@ -1,41 +1,73 @@
 use super::*;
 
-impl SliceIndexExt<str> for std::ops::RangeFrom<usize> {
+impl SliceIndexExt<str> for core::ops::RangeFrom<usize> {
     type Output = str;
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        s: &'a str,
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, s: &str, msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked(self) };
+        }
+
         let range = self.start..s.len();
-        s.get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(s, range, msg) })
+        match s.get(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(s, range.start, range.end, msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        s: &'a mut str,
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, s: &mut str, msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked_mut(self) };
+        }
+
         let range = self.start..s.len();
         let _s: *const str = &*s;
-        s.get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(&*_s, range, msg) })
+        match s.get_mut(self) {
+            Some(output) => output,
+            None => unreachable_dbg_range(unsafe { &*_s }, range.start, range.end, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, s: &'a str) -> Result<&'a Self::Output, IndexError<'a>> {
+        let range = self.start..s.len();
+        match s.get(self) {
+            Some(output) => Ok(output),
+            None => Err(range_error(s, range)),
+        }
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, s: &'a mut str) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        if s.get(self.start..).is_none() {
+            return Err(range_error(s, self.start..s.len()));
+        }
+        // SAFETY: the index was checked above.
+        Ok(unsafe { s.get_unchecked_mut(self) })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let string = "föo";
 
-        let do_test = |idx: std::ops::RangeFrom<usize>, res: &str| {
+        let do_test = |idx: core::ops::RangeFrom<usize>, res: &str| {
             assert_eq!(unsafe { string.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked(idx.clone()) }, res);
             assert_eq!(string.get(idx.clone()), Some(res));
@@ -48,52 +80,56 @@ mod tests {
         do_test(4.., "");
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob() {
         let string = "föo";
         assert!(string.get(5..).is_none());
         let _ = unsafe { string.get_unchecked_dbg(5..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_matches_std() {
         let string = "föo";
-        let _ = &string[5..];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(5..) }),
+            panic_message(|| &string[5..])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..).is_none());
         let _ = unsafe { string.get_unchecked_dbg(2..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary_matches_std() {
         let string = "föo";
-        let _ = &string[2..];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(2..) }),
+            panic_message(|| &string[2..])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob() {
         let string = "föo";
         assert!(string.get(5..).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(5.., "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..).is_none());
@@ -105,7 +141,7 @@ mod tests {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
 
-        let mut do_test = |idx: std::ops::RangeFrom<usize>, res: &mut str| {
+        let mut do_test = |idx: core::ops::RangeFrom<usize>, res: &mut str| {
             assert_eq!(unsafe { string.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(string.get_mut(idx.clone()), Some(&mut *res));
@@ -118,9 +154,9 @@ mod tests {
         do_test(4.., "".to_string().as_mut_str());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -128,18 +164,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(5..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[5..];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(5..) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[5..];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -147,18 +189,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(2..) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[2..];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(2..) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[2..];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -166,13 +214,45 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(5.., "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
         assert!(string.get_mut(2..).is_none());
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(2.., "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+
+        let mut do_test = |idx: core::ops::RangeFrom<usize>, res: Result<&str, &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                string.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                string
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(string.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1.., Ok("öo"));
+        do_test(4.., Ok(""));
+        do_test(5.., Err("start byte index 5 is out of bounds of `föo`"));
+        do_test(
+            2..,
+            Err(
+                "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`",
+            ),
+        );
+    }
 }
@@ -1,41 +1,73 @@
 use super::*;
 
-impl SliceIndexExt<str> for std::ops::RangeInclusive<usize> {
+impl SliceIndexExt<str> for core::ops::RangeInclusive<usize> {
     type Output = str;
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        s: &'a str,
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = range_inclusive_into_range(self.clone());
-        s.get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(s, range, msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, s: &str, msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked(self) };
+        }
+
+        match range_inclusive_into_range(&self).and_then(|range| s.get(range)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(s, self, msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        s: &'a mut str,
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = range_inclusive_into_range(self.clone());
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, s: &mut str, msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked_mut(self) };
+        }
+
         let _s: *const str = &*s;
-        s.get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(&*_s, range, msg) })
+        match range_inclusive_into_range(&self).and_then(|range| s.get_mut(range)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(unsafe { &*_s }, self, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, s: &'a str) -> Result<&'a Self::Output, IndexError<'a>> {
+        match range_inclusive_into_range(&self).and_then(|range| s.get(range)) {
+            Some(output) => Ok(output),
+            None => Err(range_inclusive_error(s, &self)),
+        }
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, s: &'a mut str) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        if range_inclusive_into_range(&self)
+            .and_then(|range| s.get(range))
+            .is_none()
+        {
+            return Err(range_inclusive_error(s, &self));
+        }
+        // SAFETY: the index was checked above.
+        Ok(unsafe { s.get_unchecked_mut(self) })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let string = "föo";
 
-        let do_test = |idx: std::ops::RangeInclusive<usize>, res: &str| {
+        let do_test = |idx: core::ops::RangeInclusive<usize>, res: &str| {
             assert_eq!(unsafe { string.get_unchecked_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked(idx.clone()) }, res);
             assert_eq!(string.get(idx.clone()), Some(res));
@@ -52,120 +84,192 @@ mod tests {
         do_test(3..=3, "o");
     }
 
-    #[cfg(debug_assertions)]
+    /// Returns `range` after iterating it to the end.
+    fn exhausted(mut range: core::ops::RangeInclusive<usize>) -> core::ops::RangeInclusive<usize> {
+        range.by_ref().for_each(drop);
+        assert!(range.is_empty());
+        range
+    }
+
+    #[test]
+    fn get_unchecked_dbg_exhausted_success() {
+        let string = "föo";
+
+        let do_test = |idx: core::ops::RangeInclusive<usize>| {
+            assert_eq!(unsafe { string.get_unchecked_dbg(idx.clone()) }, "");
+            assert_eq!(string.get(idx.clone()), Some(""));
+            assert_eq!(&string[idx.clone()], "");
+        };
+
+        do_test(exhausted(0..=0));
+        do_test(exhausted(1..=2));
+        do_test(exhausted(2..=3));
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
+    fn get_unchecked_dbg_exhausted_failure_oob() {
+        let string = "föo";
+        let idx = exhausted(4..=4);
+        assert!(string.get(idx.clone()).is_none());
+        let _ = unsafe { string.get_unchecked_dbg(idx) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_exhausted_failure_oob_matches_std() {
+        let string = "föo";
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(exhausted(4..=4)) }),
+            panic_message(|| &string[exhausted(4..=4)])
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index str up to maximum usize"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    fn get_unchecked_dbg_exhausted_failure_character_boundary() {
+        let string = "föo";
+        let idx = exhausted(1..=1);
+        assert!(string.get(idx.clone()).is_none());
+        let _ = unsafe { string.get_unchecked_dbg(idx) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn get_unchecked_dbg_exhausted_failure_character_boundary_matches_std() {
+        let string = "föo";
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(exhausted(1..=1)) }),
+            panic_message(|| &string[exhausted(1..=1)])
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "end byte index 18446744073709551615 is out of bounds of `föo`"]
     fn get_unchecked_dbg_usize_overflow() {
         let string = "föo";
         let _ = unsafe { string.get_unchecked_dbg(0..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index str up to maximum usize"]
     fn get_unchecked_dbg_usize_overflow_matches_std() {
         let string = "föo";
-        let _ = &string[0..=usize::MAX];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(0..=usize::MAX) }),
+            panic_message(|| &string[0..=usize::MAX])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_start() {
         let string = "föo";
         assert!(string.get(4..=4).is_none());
         let _ = unsafe { string.get_unchecked_dbg(4..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_start_matches_std() {
         let string = "föo";
-        let _ = &string[4..=4];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(4..=4) }),
+            panic_message(|| &string[4..=4])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_end() {
         let string = "föo";
         assert!(string.get(0..=4).is_none());
         let _ = unsafe { string.get_unchecked_dbg(0..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_end_matches_std() {
         let string = "föo";
-        let _ = &string[0..=4];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(0..=4) }),
+            panic_message(|| &string[0..=4])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`"]
+    #[should_panic = "begin > end (2 > 1) when slicing `föo`"]
     fn get_unchecked_dbg_failure_begin_greater_than_end() {
         let string = "föo";
         assert!(string.get(2..=0).is_none());
         let _ = unsafe { string.get_unchecked_dbg(2..=0) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`"]
     fn get_unchecked_dbg_failure_begin_greater_than_end_matches_std() {
         let string = "föo";
-        let _ = &string[2..=0];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(2..=0) }),
+            panic_message(|| &string[2..=0])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..=2).is_none());
         let _ = unsafe { string.get_unchecked_dbg(2..=2) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary_matches_std() {
         let string = "föo";
-        let _ = &string[2..=2];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(2..=2) }),
+            panic_message(|| &string[2..=2])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob_start() {
         let string = "föo";
         assert!(string.get(5..=5).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(5..=5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob_end() {
         let string = "föo";
         assert!(string.get(0..=4).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(0..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`: invalid range"]
+    #[should_panic = "begin > end (2 > 1) when slicing `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_begin_greater_than_end() {
         let string = "föo";
         assert!(string.get(2..=0).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(2..=0, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(2..=2).is_none());
@@ -177,7 +281,7 @@ mod tests {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
 
-        let mut do_test = |idx: std::ops::RangeInclusive<usize>, res: &mut str| {
+        let mut do_test = |idx: core::ops::RangeInclusive<usize>, res: &mut str| {
             assert_eq!(unsafe { string.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(string.get_mut(idx.clone()), Some(&mut *res));
@@ -194,9 +298,9 @@ mod tests {
         do_test(3..=3, "o".to_string().as_mut_str());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -204,18 +308,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(5..=5) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[5..=5];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(5..=5) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[5..=5];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -223,18 +333,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(0..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_end_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[0..=4];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(0..=4) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[0..=4];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`"]
+    #[should_panic = "begin > end (2 > 1) when slicing `föo`"]
     fn get_unchecked_mut_dbg_failure_begin_greater_than_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -242,18 +358,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(2..=0) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`"]
     fn get_unchecked_mut_dbg_failure_begin_greater_than_end_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[2..=0];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(2..=0) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[2..=0];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -261,18 +383,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(2..=2) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[2..=2];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(2..=2) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[2..=2];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "start byte index 5 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob_start() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -280,9 +408,9 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(5..=5, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -290,9 +418,9 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(0..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "begin <= end (2 <= 1) when slicing `föo`: invalid range"]
+    #[should_panic = "begin > end (2 > 1) when slicing `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_begin_greater_than_end() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -300,13 +428,78 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(2..=0, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
         assert!(string.get_mut(2..=2).is_none());
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(2..=2, "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+
+        let mut do_test = |idx: core::ops::RangeInclusive<usize>, res: Result<&str, &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                string.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                string
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(string.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(1..=2, Ok("ö"));
+        do_test(0..=4, Err("end byte index 4 is out of bounds of `föo`"));
+        do_test(2..=0, Err("begin > end (2 > 1) when slicing `föo`"));
+        do_test(
+            2..=2,
+            Err(
+                "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`",
+            ),
+        );
+        do_test(
+            0..=usize::MAX,
+            Err("end byte index 18446744073709551615 is out of bounds of `föo`"),
+        );
+        do_test(exhausted(2..=3), Ok(""));
+        do_test(
+            exhausted(4..=4),
+            Err("end byte index 4 is out of bounds of `föo`"),
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn try_get_matches_std() {
+        let string = "föo";
+
+        let do_test = |idx: core::ops::RangeInclusive<usize>| {
+            assert_eq!(
+                string.try_get(idx.clone()).unwrap_err().to_string(),
+                panic_message(|| &string[idx])
+            );
+        };
+
+        do_test(0..=4);
+        do_test(5..=5);
+        do_test(6..=1);
+        do_test(2..=0);
+        do_test(2..=2);
+        do_test(0..=1);
+        do_test(0..=usize::MAX);
+        do_test(exhausted(1..=1));
+        do_test(exhausted(4..=4));
+        do_test(exhausted(5..=5));
+    }
 }
@@ -1,41 +1,63 @@
 use super::*;
 
-impl SliceIndexExt<str> for std::ops::RangeToInclusive<usize> {
+impl SliceIndexExt<str> for core::ops::RangeToInclusive<usize> {
     type Output = str;
 
     #[inline]
-    unsafe fn get_unchecked_dbg<'a>(
-        self,
-        s: &'a str,
-        msg: Option<&'static str>,
-    ) -> &'a Self::Output {
-        let range = range_inclusive_into_range(0..=self.end);
-        s.get(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(s, range, msg) })
+    #[track_caller]
+    unsafe fn get_unchecked_dbg<M>(self, s: &str, msg: M) -> &Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked(self) };
+        }
+
+        match self.end.checked_add(1).and_then(|end| s.get(..end)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(s, 0..=self.end, msg),
+        }
     }
 
     #[inline]
-    unsafe fn get_unchecked_mut_dbg<'a>(
-        self,
-        s: &'a mut str,
-        msg: Option<&'static str>,
-    ) -> &'a mut Self::Output {
-        let range = range_inclusive_into_range(0..=self.end);
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg<M>(self, s: &mut str, msg: M) -> &mut Self::Output
+    where
+        M: DbgMessage,
+    {
+        if !check() {
+            return unsafe { s.get_unchecked_mut(self) };
+        }
+
         let _s: *const str = &*s;
-        s.get_mut(self)
-            .unwrap_or_else(|| unsafe { unreachable_dbg_range(&*_s, range, msg) })
+        match self.end.checked_add(1).and_then(|end| s.get_mut(..end)) {
+            Some(output) => output,
+            None => unreachable_dbg_range_inclusive(unsafe { &*_s }, 0..=self.end, msg),
+        }
+    }
+
+    #[inline]
+    fn try_get<'a>(self, s: &'a str) -> Result<&'a Self::Output, IndexError<'a>> {
+        (0..=self.end).try_get(s)
+    }
+
+    #[inline]
+    fn try_get_mut<'a>(self, s: &'a mut str) -> Result<&'a mut Self::Output, IndexError<'a>> {
+        (0..=self.end).try_get_mut(s)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_message;
 
     #[test]
     fn get_unchecked_dbg_success() {
         let string = "föo";
 
-        let do_test = |idx: std::ops::RangeToInclusive<usize>, res: &str| {
+        let do_test = |idx: core::ops::RangeToInclusive<usize>, res: &str| {
             assert_eq!(unsafe { string.get_unchecked_dbg(idx) }, res);
             assert_eq!(unsafe { string.get_unchecked(idx) }, res);
             assert_eq!(string.get(idx), Some(res));
@@ -47,68 +69,74 @@ mod tests {
         do_test(..=3, "föo");
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index str up to maximum usize"]
+    #[should_panic = "end byte index 18446744073709551615 is out of bounds of `föo`"]
     fn get_unchecked_dbg_usize_overflow() {
         let string = "föo";
         let _ = unsafe { string.get_unchecked_dbg(..=usize::MAX) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "attempted to index str up to maximum usize"]
     fn get_unchecked_dbg_usize_overflow_matches_std() {
         let string = "föo";
-        let _ = &string[..=usize::MAX];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(..=usize::MAX) }),
+            panic_message(|| &string[..=usize::MAX])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob() {
         let string = "föo";
         assert!(string.get(..=4).is_none());
         let _ = unsafe { string.get_unchecked_dbg(..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_dbg_failure_oob_matches_std() {
         let string = "föo";
-        let _ = &string[..=4];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(..=4) }),
+            panic_message(|| &string[..=4])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(..=1).is_none());
         let _ = unsafe { string.get_unchecked_dbg(..=1) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_dbg_failure_character_boundary_matches_std() {
         let string = "föo";
-        let _ = &string[..=1];
+        assert_eq!(
+            panic_message(|| unsafe { string.get_unchecked_dbg(..=1) }),
+            panic_message(|| &string[..=1])
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_oob() {
         let string = "föo";
         assert!(string.get(..=4).is_none());
         let _ = unsafe { string.get_unchecked_dbg_msg(..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_dbg_msg_failure_character_boundary() {
         let string = "föo";
         assert!(string.get(..=1).is_none());
@@ -120,7 +148,7 @@ mod tests {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
 
-        let mut do_test = |idx: std::ops::RangeToInclusive<usize>, res: &mut str| {
+        let mut do_test = |idx: core::ops::RangeToInclusive<usize>, res: &mut str| {
             assert_eq!(unsafe { string.get_unchecked_mut_dbg(idx.clone()) }, res);
             assert_eq!(unsafe { string.get_unchecked_mut(idx.clone()) }, res);
             assert_eq!(string.get_mut(idx.clone()), Some(&mut *res));
@@ -132,9 +160,9 @@ mod tests {
         do_test(..=3, "föo".to_string().as_mut_str());
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -142,18 +170,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(..=4) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`"]
     fn get_unchecked_mut_dbg_failure_oob_start_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[..=4];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(..=4) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[..=4];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
+    #[should_panic = "end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -161,18 +195,24 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg(..=1) };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"]
     fn get_unchecked_mut_dbg_failure_character_boundary_matches_std() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
-        let _ = &mut string[..=1];
+        assert_eq!(
+            panic_message(|| {
+                let _ = unsafe { string.get_unchecked_mut_dbg(..=1) };
+            }),
+            panic_message(|| {
+                let _ = &mut string[..=1];
+            })
+        );
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 5 is out of bounds of `föo`: invalid range"]
+    #[should_panic = "end byte index 4 is out of bounds of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_oob() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
@@ -180,13 +220,46 @@ mod tests {
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(..=4, "invalid range") };
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
     #[test]
-    #[should_panic = "byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
+    #[should_panic = "end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`: invalid range"]
     fn get_unchecked_mut_dbg_msg_failure_character_boundary() {
         let mut string = "föo".to_string();
         let string = string.as_mut_str();
         assert!(string.get_mut(..=1).is_none());
         let _ = unsafe { string.get_unchecked_mut_dbg_msg(..=1, "invalid range") };
     }
+
+    #[test]
+    fn try_get() {
+        let mut string = "föo".to_string();
+        let string = string.as_mut_str();
+
+        let mut do_test = |idx: core::ops::RangeToInclusive<usize>, res: Result<&str, &str>| {
+            let res = res.map_err(str::to_string);
+            assert_eq!(
+                string.try_get(idx.clone()).map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(
+                string
+                    .try_get_mut(idx.clone())
+                    .map(|output| &*output)
+                    .map_err(|err| err.to_string()),
+                res
+            );
+            assert_eq!(string.get(idx).is_some(), res.is_ok());
+        };
+
+        do_test(..=2, Ok("fö"));
+        do_test(..=4, Err("end byte index 4 is out of bounds of `föo`"));
+        do_test(
+            ..=1,
+            Err("end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`"),
+        );
+        do_test(
+            ..=usize::MAX,
+            Err("end byte index 18446744073709551615 is out of bounds of `föo`"),
+        );
+    }
 }
@@ -1,17 +1,23 @@
+mod dbg_str;
 mod index_range;
 mod index_range_from;
 mod index_range_inclusive;
 mod index_range_to;
 mod index_range_to_inclusive;
 
-use crate::{SliceIndexExt, unreachable_dbg_fmt, unreachable_dbg_msg};
+pub use dbg_str::DbgStr;
+
+use crate::{
+    DbgMessage, IndexError, Indexed, SliceIndexExt, Violation, config::check,
+    slice::range_inclusive_into_range, violation::violated_msg,
+};
 
 /// An extension trait for [`str`](https://doc.rust-lang.org/std/primitive.str.html)
 /// which provides alternatives to [`get_unchecked()`](str::get_unchecked) / [`get_unchecked_mut()`](str::get_unchecked_mut)
 /// which panic in debug configuration in case the index is invalid, with an optional custom message.
 ///
-/// Implemented for [`std::ops::Range<usize>`], [`std::ops::RangeInclusive<usize>`],
-/// [`std::ops::RangeFrom<usize>`], [`std::ops::RangeTo<usize>`], [`std::ops::RangeToInclusive<usize>`].
+/// Implemented for [`core::ops::Range<usize>`], [`core::ops::RangeInclusive<usize>`],
+/// [`core::ops::RangeFrom<usize>`], [`core::ops::RangeTo<usize>`], [`core::ops::RangeToInclusive<usize>`].
 pub trait StrExt {
     /// Alternative to [`get_unchecked()`](str::get_unchecked)
     /// which panics in debug configuration in case the index is invalid.
@@ -63,19 +69,113 @@ pub trait StrExt {
     ) -> &mut <I as SliceIndexExt<str>>::Output
     where
         I: SliceIndexExt<str>;
+
+    /// Alternative to [`get_unchecked()`](str::get_unchecked)
+    /// which panics in debug configuration in case the index is invalid, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked()`](str::get_unchecked) documentation.
+    unsafe fn get_unchecked_dbg_fmt<I>(
+        &self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &<I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>;
+
+    /// Alternative to [`get_unchecked_mut()`](str::get_unchecked_mut)
+    /// which panics in debug configuration in case the index is invalid, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked_mut()`](str::get_unchecked_mut) documentation.
+    unsafe fn get_unchecked_mut_dbg_fmt<I>(
+        &mut self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &mut <I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>;
+
+    /// Alternative to [`get_unchecked()`](str::get_unchecked)
+    /// which panics in debug configuration in case the index is invalid,
+    /// with a custom error message returned by `f`, which is only called if the index is invalid.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked()`](str::get_unchecked) documentation.
+    unsafe fn get_unchecked_dbg_with<I, F, D>(
+        &self,
+        index: I,
+        f: F,
+    ) -> &<I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display;
+
+    /// Alternative to [`get_unchecked_mut()`](str::get_unchecked_mut)
+    /// which panics in debug configuration in case the index is invalid,
+    /// with a custom error message returned by `f`, which is only called if the index is invalid.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_unchecked_mut()`](str::get_unchecked_mut) documentation.
+    unsafe fn get_unchecked_mut_dbg_with<I, F, D>(
+        &mut self,
+        index: I,
+        f: F,
+    ) -> &mut <I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display;
+
+    /// Alternative to [`get()`](str::get) which returns the reason why the index is invalid, if it is.
+    fn try_get<I>(&self, index: I) -> Result<&<I as SliceIndexExt<str>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<str>;
+
+    /// Alternative to [`get_mut()`](str::get_mut) which returns the reason why the index is invalid, if it is.
+    fn try_get_mut<I>(
+        &mut self,
+        index: I,
+    ) -> Result<&mut <I as SliceIndexExt<str>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<str>;
+
+    /// Wraps the string slice in a [`DbgStr`], whose square brackets indexing operator panics in debug configuration
+    /// in case the index is invalid, but doesn't check the index in release configuration.
+    ///
+    /// # Safety
+    ///
+    /// Every index the wrapper is ever indexed with (also by any code it is passed to) must be valid,
+    /// see [`DbgStr::new()`] documentation.
+    unsafe fn dbg(&self) -> &DbgStr;
+
+    /// Same as [`dbg()`](StrExt::dbg), but wraps the mutable string slice.
+    ///
+    /// # Safety
+    ///
+    /// Every index the wrapper is ever indexed with (also by any code it is passed to) must be valid,
+    /// see [`DbgStr::new_mut()`] documentation.
+    unsafe fn dbg_mut(&mut self) -> &mut DbgStr;
 }
 
 impl StrExt for str {
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_dbg<I>(&self, index: I) -> &<I as SliceIndexExt<str>>::Output
     where
         I: SliceIndexExt<str>,
     {
         // See `str::get_unchecked()`
-        &*unsafe { index.get_unchecked_dbg(self, None) }
+        unsafe { index.get_unchecked_dbg(self, None) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_mut_dbg<I>(
         &mut self,
         index: I,
@@ -84,10 +184,11 @@ impl StrExt for str {
         I: SliceIndexExt<str>,
     {
         // See `str::get_unchecked_mut()`
-        &mut *unsafe { index.get_unchecked_mut_dbg(self, None) }
+        unsafe { index.get_unchecked_mut_dbg(self, None) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_dbg_msg<I>(
         &self,
         index: I,
@@ -97,10 +198,11 @@ impl StrExt for str {
         I: SliceIndexExt<str>,
     {
         // See `str::get_unchecked()`
-        &*unsafe { index.get_unchecked_dbg(self, Some(msg)) }
+        unsafe { index.get_unchecked_dbg(self, Some(msg)) }
     }
 
     #[inline]
+    #[track_caller]
     unsafe fn get_unchecked_mut_dbg_msg<I>(
         &mut self,
         index: I,
@@ -110,7 +212,98 @@ impl StrExt for str {
         I: SliceIndexExt<str>,
     {
         // See `str::get_unchecked_mut()`
-        &mut *unsafe { index.get_unchecked_mut_dbg(self, Some(msg)) }
+        unsafe { index.get_unchecked_mut_dbg(self, Some(msg)) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_dbg_fmt<I>(
+        &self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &<I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+    {
+        // See `str::get_unchecked()`
+        unsafe { index.get_unchecked_dbg(self, fmt) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg_fmt<I>(
+        &mut self,
+        index: I,
+        fmt: core::fmt::Arguments<'_>,
+    ) -> &mut <I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+    {
+        // See `str::get_unchecked_mut()`
+        unsafe { index.get_unchecked_mut_dbg(self, fmt) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_dbg_with<I, F, D>(
+        &self,
+        index: I,
+        f: F,
+    ) -> &<I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display,
+    {
+        // See `str::get_unchecked()`
+        unsafe { index.get_unchecked_dbg(self, f) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn get_unchecked_mut_dbg_with<I, F, D>(
+        &mut self,
+        index: I,
+        f: F,
+    ) -> &mut <I as SliceIndexExt<str>>::Output
+    where
+        I: SliceIndexExt<str>,
+        F: FnOnce() -> D,
+        D: core::fmt::Display,
+    {
+        // See `str::get_unchecked_mut()`
+        unsafe { index.get_unchecked_mut_dbg(self, f) }
+    }
+
+    #[inline]
+    fn try_get<I>(&self, index: I) -> Result<&<I as SliceIndexExt<str>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<str>,
+    {
+        index.try_get(self)
+    }
+
+    #[inline]
+    fn try_get_mut<I>(
+        &mut self,
+        index: I,
+    ) -> Result<&mut <I as SliceIndexExt<str>>::Output, IndexError<'_>>
+    where
+        I: SliceIndexExt<str>,
+    {
+        index.try_get_mut(self)
+    }
+
+    #[inline]
+    unsafe fn dbg(&self) -> &DbgStr {
+        // See `DbgStr::new()`
+        unsafe { DbgStr::new(self) }
+    }
+
+    #[inline]
+    unsafe fn dbg_mut(&mut self) -> &mut DbgStr {
+        // See `DbgStr::new_mut()`
+        unsafe { DbgStr::new_mut(self) }
     }
 }
 
@@ -123,7 +316,7 @@ const fn is_utf8_char_boundary(b: u8) -> bool {
 
 /// Copied from `str::floor_char_boundary`. Unstable, gated by "round_char_boundary".
 /// TODO: get rid of when `str::floor_char_boundary` is stabilized.
-fn floor_char_boundary(s: &str, index: usize) -> usize {
+pub(crate) fn floor_char_boundary(s: &str, index: usize) -> usize {
     if index >= s.len() {
         s.len()
     } else {
@@ -137,104 +330,125 @@ fn floor_char_boundary(s: &str, index: usize) -> usize {
     }
 }
 
-pub(super) unsafe fn unreachable_dbg_range(
-    s: &str,
-    range: std::ops::Range<usize>,
-    msg: Option<&'static str>,
-) -> ! {
+/// Reports the invalid `start..end` range into `s`.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(super) fn unreachable_dbg_range(s: &str, start: usize, end: usize, msg: impl DbgMessage) -> ! {
+    violated_msg(range_violation(s, start..end), msg)
+}
+
+/// Returns the error for the invalid `range` into `s`.
+pub(super) fn range_error(s: &str, range: core::ops::Range<usize>) -> IndexError<'_> {
+    IndexError::new(range_violation(s, range))
+}
+
+/// Returns the reason why `range` is not a valid index into `s`.
+///
+/// `range` must be invalid.
+pub(super) fn range_violation(s: &str, range: core::ops::Range<usize>) -> Violation<'_> {
     // Logic copied from `str::slice_error_fail_rt` with minor changes. Private.
     //
     // NOTE: when `end` is out of bounds, this code reports an off-by-one out of bounds index.
     // E.g. if a string is "foo", 3 bytes long, and `range` is `0..4`
     // (i.e. it includes bytes `0` ("f"), `1` ("o"), `2` ("o"), and `3` (actual out-of-bounds index)),
-    // this will report that "byte index 4 is out of bounds of `foo`", which is wrong, but this is the default std behaviour,
+    // this will report that "end byte index 4 is out of bounds of `foo`", which is wrong, but this is the default std behaviour,
     // and I want this code to mimic it.
-    //
-    // NOTE: when `begin > end`, this seemingly reports an "inverted" error message (namely that `begin <= end`) for some reason.
-    // As in this reports the assert condition, instead of the error which happened, which is counterintuitive and weird.
-    // Keeping the default behaviour, as above.
 
     let begin = range.start;
     let end = range.end;
 
-    const MAX_DISPLAY_LENGTH: usize = 256;
-    let trunc_len = floor_char_boundary(s, MAX_DISPLAY_LENGTH);
-    let s_trunc = &s[..trunc_len];
-    let ellipsis = if trunc_len < s.len() { "[...]" } else { "" };
-
     // 1. out of bounds
-    if begin > s.len() || end > s.len() {
-        let oob_index = if begin > s.len() { begin } else { end };
-        //panic!("byte index {oob_index} is out of bounds of `{s_trunc}`{ellipsis}");
-        unsafe {
-            unreachable_dbg_fmt(format_args!(
-                "byte index {oob_index} is out of bounds of `{s_trunc}`{ellipsis}{}{}",
-                if msg.is_some() { ": " } else { "" },
-                if let Some(msg) = msg { msg } else { "" }
-            ))
-        }
+    if begin > s.len() {
+        return Violation::RangeStartOutOfBounds {
+            start: begin,
+            indexed: Indexed::Str(s),
+        };
+    }
+    if end > s.len() {
+        return Violation::RangeEndOutOfBounds {
+            end,
+            indexed: Indexed::Str(s),
+        };
     }
 
     // 2. begin <= end
-    // assert!(
-    //     begin <= end,
-    //     "begin <= end ({} <= {}) when slicing `{}`{}",
-    //     begin,
-    //     end,
-    //     s_trunc,
-    //     ellipsis
-    // );
     if begin > end {
-        unsafe {
-            unreachable_dbg_fmt(format_args!(
-                "begin <= end ({} <= {}) when slicing `{s_trunc}`{ellipsis}{}{}",
-                begin,
-                end,
-                if msg.is_some() { ": " } else { "" },
-                if let Some(msg) = msg { msg } else { "" }
-            ))
-        }
+        return Violation::RangeInverted {
+            start: begin,
+            end,
+            indexed: Indexed::Str(s),
+        };
     }
 
     // 3. character boundary
-    let index = if !s.is_char_boundary(begin) {
-        begin
-    } else {
-        end
-    };
+    let is_start = !s.is_char_boundary(begin);
+    let index = if is_start { begin } else { end };
     // find the character
     let char_start = floor_char_boundary(s, index);
     // `char_start` must be less than len and a char boundary
     let ch = s[char_start..].chars().next().unwrap();
     let char_range = char_start..char_start + ch.len_utf8();
-    // panic!(
-    //     "byte index {} is not a char boundary; it is inside {:?} (bytes {:?}) of `{}`{}",
-    //     index, ch, char_range, s_trunc, ellipsis
-    // );
-    unsafe {
-        unreachable_dbg_fmt(format_args!(
-            "byte index {} is not a char boundary; it is inside {:?} (bytes {:?}) of `{s_trunc}`{ellipsis}{}{}",
-            index,
-            ch,
-            char_range,
-            if msg.is_some() { ": " } else { "" },
-            if let Some(msg) = msg { msg } else { "" }
-        ))
+
+    Violation::NotCharBoundary {
+        index,
+        is_start,
+        char: ch,
+        char_range,
+        s,
     }
 }
 
-/// Based on `str::traits::str_index_overflow_fail`.
-fn str_index_overflow_fail() -> ! {
-    unsafe { unreachable_dbg_msg("attempted to index str up to maximum usize") };
+/// Reports the invalid inclusive `range` into `s`.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(super) fn unreachable_dbg_range_inclusive(
+    s: &str,
+    range: core::ops::RangeInclusive<usize>,
+    msg: impl DbgMessage,
+) -> ! {
+    violated_msg(range_inclusive_violation(s, &range), msg)
 }
 
-/// Based on `std::ops::RangeInclusive<usize>::into_slice_range`, but does not handle exhausted ranges.
-/// TODO: revisit exhausted handling.
-pub(super) fn range_inclusive_into_range(
-    range: std::ops::RangeInclusive<usize>,
-) -> std::ops::Range<usize> {
-    if *range.end() == usize::MAX {
-        str_index_overflow_fail();
+/// Returns the error for the invalid inclusive `range` into `s`.
+pub(super) fn range_inclusive_error<'a>(
+    s: &'a str,
+    range: &core::ops::RangeInclusive<usize>,
+) -> IndexError<'a> {
+    IndexError::new(range_inclusive_violation(s, range))
+}
+
+/// Returns the reason why the inclusive `range` is not a valid index into `s`.
+///
+/// `range` must be invalid.
+pub(super) fn range_inclusive_violation<'a>(
+    s: &'a str,
+    range: &core::ops::RangeInclusive<usize>,
+) -> Violation<'a> {
+    // Like the standard library, reports the inclusive end rather than the exclusive one it converts it to,
+    // also for an exhausted range, when it is out of bounds.
+    if *range.start() > s.len() {
+        Violation::RangeStartOutOfBounds {
+            start: *range.start(),
+            indexed: Indexed::Str(s),
+        }
+    } else if *range.end() == usize::MAX {
+        Violation::IndexOverflow {
+            indexed: Indexed::Str(s),
+        }
+    } else if *range.end() >= s.len() {
+        Violation::RangeEndOutOfBounds {
+            end: *range.end(),
+            indexed: Indexed::Str(s),
+        }
+    } else {
+        // The range can only be inverted or not on a character boundary here, which the exclusive range reports.
+        match range_inclusive_into_range(range) {
+            Some(range) => range_violation(s, range),
+            None => Violation::IndexOverflow {
+                indexed: Indexed::Str(s),
+            },
+        }
     }
-    *range.start()..*range.end() + 1
 }
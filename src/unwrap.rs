@@ -0,0 +1,401 @@
+use {
+    crate::{OptionExt, ResultExt, Violation, config::check, violation::violated},
+    core::{fmt, ops::ControlFlow, task::Poll},
+};
+
+/// A type which may or may not hold an [`Output`](UncheckedUnwrap::Output) value,
+/// which [`unwrap_output_unchecked_dbg()`](UncheckedUnwrap::unwrap_output_unchecked_dbg) and [`unwrap_unchecked_dbg!`](crate::unwrap_unchecked_dbg)
+/// unwrap, panicking in debug configuration if there is none.
+///
+/// Implemented for [`Option`] and [`Result`] (via [`OptionExt`] / [`ResultExt`]), [`Poll`] and [`ControlFlow`]
+/// (whose output is the [`Continue`](ControlFlow::Continue) value). Other types may implement it by providing
+/// [`try_unwrap()`](UncheckedUnwrap::try_unwrap) and [`describe_failure()`](UncheckedUnwrap::describe_failure);
+/// the panic message is then the failure description, followed by the custom message, if any
+/// (the reported violation is [`Violation::Unwrapped`]).
+///
+/// # Examples
+///
+/// ```
+/// use {core::fmt, miniunchecked::{UncheckedUnwrap, unwrap_unchecked_dbg}};
+///
+/// enum Slot<T> {
+///     Empty,
+///     Full(T),
+/// }
+///
+/// impl<T> UncheckedUnwrap for Slot<T> {
+///     type Output = T;
+///
+///     fn try_unwrap(self) -> Result<T, Self> {
+///         match self {
+///             Slot::Full(val) => Ok(val),
+///             Slot::Empty => Err(self),
+///         }
+///     }
+///
+///     fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("called `Slot::unwrap()` on an `Empty` slot")
+///     }
+/// }
+///
+/// let val = unsafe { unwrap_unchecked_dbg!(Slot::Full(7), "the slot was just filled") };
+/// assert_eq!(val, 7);
+///
+/// let val = unsafe { Slot::Full(7).unwrap_output_unchecked_dbg() };
+/// assert_eq!(val, 7);
+/// ```
+pub trait UncheckedUnwrap: Sized {
+    /// The type of the unwrapped value.
+    type Output;
+
+    /// Returns the output, or `self` if there is none.
+    fn try_unwrap(self) -> Result<Self::Output, Self>;
+
+    /// Describes the failed unwrap of `self`, e.g. "called `Option::unwrap()` on a `None` value".
+    ///
+    /// Only called on values without an output, i.e. the ones [`try_unwrap()`](UncheckedUnwrap::try_unwrap) returns as [`Err`].
+    /// The implementations may assume so, e.g. the ones for [`Result`] and [`ControlFlow`] panic if there is an output.
+    fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Returns the output, panicking in debug configuration if there is none.
+    ///
+    /// # Safety
+    ///
+    /// There must be an output, i.e. [`try_unwrap()`](UncheckedUnwrap::try_unwrap) must return [`Ok`].
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg(self) -> Self::Output {
+        if !check() {
+            return unsafe { unwrap_unchecked(self) };
+        }
+
+        match self.try_unwrap() {
+            Ok(output) => output,
+            Err(this) => unreachable_dbg_msg(&Failure(&this), None),
+        }
+    }
+
+    /// Returns the output, panicking in debug configuration if there is none, with a custom error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_output_unchecked_dbg()`](UncheckedUnwrap::unwrap_output_unchecked_dbg) documentation.
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_msg(self, msg: &'static str) -> Self::Output {
+        if !check() {
+            return unsafe { unwrap_unchecked(self) };
+        }
+
+        match self.try_unwrap() {
+            Ok(output) => output,
+            Err(this) => unreachable_dbg_msg(&Failure(&this), Some(msg)),
+        }
+    }
+
+    /// Returns the output, panicking in debug configuration if there is none, with a custom formatted error message.
+    ///
+    /// # Safety
+    ///
+    /// See [`unwrap_output_unchecked_dbg()`](UncheckedUnwrap::unwrap_output_unchecked_dbg) documentation.
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_fmt(self, fmt: fmt::Arguments<'_>) -> Self::Output {
+        if !check() {
+            return unsafe { unwrap_unchecked(self) };
+        }
+
+        match self.try_unwrap() {
+            Ok(output) => output,
+            Err(this) => unreachable_dbg_fmt(&Failure(&this), fmt),
+        }
+    }
+}
+
+impl<T> UncheckedUnwrap for Option<T> {
+    type Output = T;
+
+    #[inline]
+    fn try_unwrap(self) -> Result<T, Self> {
+        self.ok_or(None)
+    }
+
+    fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Violation::NoneUnwrapped, f)
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg(self) -> T {
+        unsafe { OptionExt::unwrap_unchecked_dbg(self) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_msg(self, msg: &'static str) -> T {
+        unsafe { OptionExt::unwrap_unchecked_dbg_msg(self, msg) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_fmt(self, fmt: fmt::Arguments<'_>) -> T {
+        unsafe { OptionExt::unwrap_unchecked_dbg_fmt(self, fmt) }
+    }
+}
+
+impl<T, E> UncheckedUnwrap for Result<T, E> {
+    type Output = T;
+
+    #[inline]
+    fn try_unwrap(self) -> Result<T, Self> {
+        self.map_err(Err)
+    }
+
+    fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ok(_) => unreachable!("`describe_failure()` called on an `Ok` value"),
+            Err(_) => fmt::Display::fmt(&Violation::ErrUnwrapped, f),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg(self) -> T {
+        unsafe { ResultExt::unwrap_unchecked_dbg(self) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_msg(self, msg: &'static str) -> T {
+        unsafe { ResultExt::unwrap_unchecked_dbg_msg(self, msg) }
+    }
+
+    #[inline]
+    #[track_caller]
+    unsafe fn unwrap_output_unchecked_dbg_fmt(self, fmt: fmt::Arguments<'_>) -> T {
+        unsafe { ResultExt::unwrap_unchecked_dbg_fmt(self, fmt) }
+    }
+}
+
+impl<T> UncheckedUnwrap for Poll<T> {
+    type Output = T;
+
+    #[inline]
+    fn try_unwrap(self) -> Result<T, Self> {
+        match self {
+            Poll::Ready(val) => Ok(val),
+            Poll::Pending => Err(Poll::Pending),
+        }
+    }
+
+    fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("called `Poll::unwrap()` on a `Pending` value")
+    }
+}
+
+impl<B, C> UncheckedUnwrap for ControlFlow<B, C>
+where
+    B: fmt::Debug,
+{
+    type Output = C;
+
+    #[inline]
+    fn try_unwrap(self) -> Result<C, Self> {
+        match self {
+            ControlFlow::Continue(val) => Ok(val),
+            ControlFlow::Break(val) => Err(ControlFlow::Break(val)),
+        }
+    }
+
+    fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlFlow::Continue(_) => {
+                unreachable!("`describe_failure()` called on a `Continue` value")
+            }
+            ControlFlow::Break(val) => {
+                write!(
+                    f,
+                    "called `ControlFlow::unwrap()` on a `Break` value: {val:?}"
+                )
+            }
+        }
+    }
+}
+
+/// Returns the output of `value` without checking that there is one.
+///
+/// # Safety
+///
+/// See [`UncheckedUnwrap::unwrap_output_unchecked_dbg()`] documentation.
+#[inline]
+unsafe fn unwrap_unchecked<U>(value: U) -> U::Output
+where
+    U: UncheckedUnwrap,
+{
+    match value.try_unwrap() {
+        Ok(output) => output,
+        // SAFETY: there is an output, as promised by the caller.
+        Err(_) => unsafe { core::hint::unreachable_unchecked() },
+    }
+}
+
+/// Formats the failure description of a value without an output.
+struct Failure<'a, U>(&'a U);
+
+impl<U> fmt::Display for Failure<'_, U>
+where
+    U: UncheckedUnwrap,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.describe_failure(f)
+    }
+}
+
+/// Reports the failed unwrap described by `failure`, with an optional custom error message.
+///
+/// Takes the description as a trait object, so that it's not instantiated for each unwrapped type.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_msg(failure: &dyn fmt::Display, msg: Option<&'static str>) -> ! {
+    match msg {
+        Some(msg) => violated(Violation::Unwrapped, Some(format_args!("{failure}: {msg}"))),
+        None => violated(Violation::Unwrapped, Some(format_args!("{failure}"))),
+    }
+}
+
+/// Same as [`unreachable_dbg_msg()`], but with a custom formatted error message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unreachable_dbg_fmt(failure: &dyn fmt::Display, fmt: fmt::Arguments<'_>) -> ! {
+    violated(Violation::Unwrapped, Some(format_args!("{failure}: {fmt}")))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    use crate::tests::panic_location;
+    use {super::*, crate::unwrap_unchecked_dbg};
+
+    struct NotDebug;
+
+    #[derive(Debug, PartialEq)]
+    enum Either {
+        Left(i32),
+        Right(&'static str),
+    }
+
+    impl UncheckedUnwrap for Either {
+        type Output = i32;
+
+        fn try_unwrap(self) -> Result<i32, Self> {
+            match self {
+                Either::Left(val) => Ok(val),
+                Either::Right(_) => Err(self),
+            }
+        }
+
+        fn describe_failure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "called `Either::unwrap_left()` on a `{self:?}` value")
+        }
+    }
+
+    #[test]
+    fn unwrap_unchecked_dbg_success() {
+        unsafe {
+            assert_eq!(unwrap_unchecked_dbg!(Either::Left(7)), 7);
+            assert_eq!(unwrap_unchecked_dbg!(Either::Left(7), "missing value"), 7);
+            assert_eq!(unwrap_unchecked_dbg!(Poll::Ready(7)), 7);
+            assert_eq!(unwrap_unchecked_dbg!(ControlFlow::<(), _>::Continue(7)), 7);
+            assert_eq!(
+                unwrap_unchecked_dbg!(Ok::<_, NotDebug>(7), "missing value"),
+                7
+            );
+            assert_eq!(Either::Left(7).unwrap_output_unchecked_dbg(), 7);
+            assert_eq!(Some(7).unwrap_output_unchecked_dbg_msg("missing value"), 7);
+            assert_eq!(Some(7).try_unwrap(), Ok(7));
+            assert_eq!(Err::<i32, _>(()).try_unwrap(), Err(Err(())));
+            assert_eq!(
+                Either::Right("seven").try_unwrap(),
+                Err(Either::Right("seven"))
+            );
+        }
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Either::unwrap_left()` on a `Right(\"seven\")` value"]
+    fn unwrap_unchecked_dbg_failure() {
+        let _ = unsafe { unwrap_unchecked_dbg!(Either::Right("seven")) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Either::unwrap_left()` on a `Right(\"seven\")` value: missing value (expected 7)"]
+    fn unwrap_unchecked_dbg_fmt_failure() {
+        let _ = unsafe {
+            unwrap_unchecked_dbg!(Either::Right("seven"), "missing value (expected {})", 7)
+        };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Either::unwrap_left()` on a `Right(\"seven\")` value: missing value"]
+    fn unwrap_output_unchecked_dbg_msg_failure() {
+        let _ = unsafe { Either::Right("seven").unwrap_output_unchecked_dbg_msg("missing value") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Poll::unwrap()` on a `Pending` value: not ready"]
+    fn unwrap_unchecked_dbg_poll_failure() {
+        let _ = unsafe { unwrap_unchecked_dbg!(Poll::<i32>::Pending, "not ready") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `ControlFlow::unwrap()` on a `Break` value: 7"]
+    fn unwrap_unchecked_dbg_control_flow_failure() {
+        let _ = unsafe { unwrap_unchecked_dbg!(ControlFlow::<i32, i32>::Break(7)) };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: (): missing value"]
+    fn unwrap_unchecked_dbg_result_failure() {
+        let x: Result<i32, ()> = Err(());
+        let _ = unsafe { unwrap_unchecked_dbg!(x, "missing value") };
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    #[should_panic = "called `Result::unwrap()` on an `Err` value: missing value (expected 7)"]
+    fn unwrap_unchecked_dbg_non_debug_result_failure() {
+        let x: Result<i32, NotDebug> = Err(NotDebug);
+        let _ = unsafe { unwrap_unchecked_dbg!(x, "missing value (expected {})", 7) };
+    }
+
+    #[test]
+    fn describe_failure_matches_std() {
+        assert_eq!(
+            Failure(&None::<i32>).to_string(),
+            "called `Option::unwrap()` on a `None` value"
+        );
+        assert_eq!(
+            Failure(&Err::<i32, _>(NotDebug)).to_string(),
+            "called `Result::unwrap()` on an `Err` value"
+        );
+    }
+
+    #[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+    #[test]
+    fn unwrap_unchecked_dbg_location() {
+        let location = panic_location(|| unsafe { unwrap_unchecked_dbg!(Either::Right("seven")) });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+
+        let location =
+            panic_location(|| unsafe { unwrap_unchecked_dbg!(Poll::<i32>::Pending, "not ready") });
+        assert_eq!(location, (file!().to_string(), line!() - 1));
+    }
+}
@@ -0,0 +1,525 @@
+use {
+    crate::hook::call_violation_hook,
+    core::{fmt, ops::Range, panic::Location},
+};
+
+/// A kind of invariant violation detected by the debug checks.
+///
+/// Its [`Display`](fmt::Display) implementation produces std-like messages: for the same errors,
+/// the same ones the current standard library (e.g. [`Option::unwrap()`], slice / string
+/// square brackets indexing operator) panics with, though std's wording may change between Rust versions.
+/// [`Unwrapped`](Violation::Unwrapped) has no standard library counterpart, so its message is only std-like.
+///
+/// Passed to the violation hook (see [`set_violation_hook()`](crate::set_violation_hook)).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Violation<'a> {
+    /// [`None`] was unwrapped.
+    NoneUnwrapped,
+    /// [`Err`] was unwrapped.
+    ErrUnwrapped,
+    /// [`Ok`] was unwrapped as an error (e.g. by [`Result::unwrap_err()`]).
+    OkUnwrapped,
+    /// [`None`] was unwrapped with an [`Option::expect()`]-like message.
+    NoneExpected,
+    /// [`Err`] was unwrapped with a [`Result::expect()`]-like message.
+    ErrExpected,
+    /// [`Ok`] was unwrapped as an error with a [`Result::expect_err()`]-like message.
+    OkExpected,
+    /// A value without an output was unwrapped via [`UncheckedUnwrap`](crate::UncheckedUnwrap);
+    /// described by the message produced by its [`describe_failure()`](crate::UncheckedUnwrap::describe_failure).
+    Unwrapped,
+    /// A slice was indexed with an out of bounds `index`.
+    IndexOutOfBounds { index: usize, len: usize },
+    /// A slice / string was indexed with a range which starts out of bounds.
+    RangeStartOutOfBounds { start: usize, indexed: Indexed<'a> },
+    /// A slice / string was indexed with a range which ends out of bounds.
+    RangeEndOutOfBounds { end: usize, indexed: Indexed<'a> },
+    /// A slice / string was indexed with a range which starts after it ends.
+    RangeInverted {
+        start: usize,
+        end: usize,
+        indexed: Indexed<'a>,
+    },
+    /// A string was indexed with a range which starts (if `is_start`) / ends at byte `index`,
+    /// which is not a character boundary, but is inside character `char` which occupies bytes `char_range`.
+    NotCharBoundary {
+        index: usize,
+        is_start: bool,
+        char: char,
+        char_range: Range<usize>,
+        s: &'a str,
+    },
+    /// A slice / string was indexed with an inclusive range which ends at [`usize::MAX`].
+    ///
+    /// Described like a range which ends out of bounds at [`usize::MAX`], like the standard library does.
+    IndexOverflow { indexed: Indexed<'a> },
+    /// Unreachable code was reached.
+    Unreachable,
+}
+
+impl Violation<'_> {
+    /// Returns the kind of the violation, without the details.
+    pub fn kind(&self) -> ViolationKind {
+        match self {
+            Violation::NoneUnwrapped => ViolationKind::NoneUnwrapped,
+            Violation::ErrUnwrapped => ViolationKind::ErrUnwrapped,
+            Violation::OkUnwrapped => ViolationKind::OkUnwrapped,
+            Violation::NoneExpected => ViolationKind::NoneExpected,
+            Violation::ErrExpected => ViolationKind::ErrExpected,
+            Violation::OkExpected => ViolationKind::OkExpected,
+            Violation::Unwrapped => ViolationKind::Unwrapped,
+            Violation::IndexOutOfBounds { .. } => ViolationKind::IndexOutOfBounds,
+            Violation::RangeStartOutOfBounds { .. } => ViolationKind::RangeStartOutOfBounds,
+            Violation::RangeEndOutOfBounds { .. } => ViolationKind::RangeEndOutOfBounds,
+            Violation::RangeInverted { .. } => ViolationKind::RangeInverted,
+            Violation::NotCharBoundary { .. } => ViolationKind::NotCharBoundary,
+            Violation::IndexOverflow { .. } => ViolationKind::IndexOverflow,
+            Violation::Unreachable => ViolationKind::Unreachable,
+        }
+    }
+
+    /// Whether the violation is described by the message alone, like [`Option::expect()`] does.
+    pub(crate) fn is_described_by_message(&self) -> bool {
+        matches!(
+            self,
+            Violation::NoneExpected
+                | Violation::ErrExpected
+                | Violation::OkExpected
+                | Violation::Unwrapped
+        )
+    }
+}
+
+/// The kind of a [`Violation`], without the details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// See [`Violation::NoneUnwrapped`].
+    NoneUnwrapped,
+    /// See [`Violation::ErrUnwrapped`].
+    ErrUnwrapped,
+    /// See [`Violation::OkUnwrapped`].
+    OkUnwrapped,
+    /// See [`Violation::NoneExpected`].
+    NoneExpected,
+    /// See [`Violation::ErrExpected`].
+    ErrExpected,
+    /// See [`Violation::OkExpected`].
+    OkExpected,
+    /// See [`Violation::Unwrapped`].
+    Unwrapped,
+    /// See [`Violation::IndexOutOfBounds`].
+    IndexOutOfBounds,
+    /// See [`Violation::RangeStartOutOfBounds`].
+    RangeStartOutOfBounds,
+    /// See [`Violation::RangeEndOutOfBounds`].
+    RangeEndOutOfBounds,
+    /// See [`Violation::RangeInverted`].
+    RangeInverted,
+    /// See [`Violation::NotCharBoundary`].
+    NotCharBoundary,
+    /// See [`Violation::IndexOverflow`].
+    IndexOverflow,
+    /// See [`Violation::Unreachable`].
+    Unreachable,
+}
+
+/// What was indexed when a [`Violation`] occured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indexed<'a> {
+    /// A slice of length `len`.
+    Slice { len: usize },
+    /// A string.
+    Str(&'a str),
+}
+
+impl Indexed<'_> {
+    /// Returns the length of the indexed slice / string (in bytes).
+    pub fn len(&self) -> usize {
+        match self {
+            Indexed::Slice { len } => *len,
+            Indexed::Str(s) => s.len(),
+        }
+    }
+
+    /// Returns `true` if the indexed slice / string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for Violation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::NoneUnwrapped => f.write_str("called `Option::unwrap()` on a `None` value"),
+            Violation::ErrUnwrapped => f.write_str("called `Result::unwrap()` on an `Err` value"),
+            Violation::OkUnwrapped => f.write_str("called `Result::unwrap_err()` on an `Ok` value"),
+            Violation::NoneExpected => f.write_str("called `Option::expect()` on a `None` value"),
+            Violation::ErrExpected => f.write_str("called `Result::expect()` on an `Err` value"),
+            Violation::OkExpected => f.write_str("called `Result::expect_err()` on an `Ok` value"),
+            Violation::Unwrapped => f.write_str("called `unwrap()` on a value without an output"),
+            Violation::IndexOutOfBounds { index, len } => write!(
+                f,
+                "index out of bounds: the len is {len} but the index is {index}"
+            ),
+            Violation::RangeStartOutOfBounds {
+                start,
+                indexed: Indexed::Slice { len },
+            } => write!(
+                f,
+                "range start index {start} out of range for slice of length {len}"
+            ),
+            Violation::RangeEndOutOfBounds {
+                end,
+                indexed: Indexed::Slice { len },
+            } => write!(
+                f,
+                "range end index {end} out of range for slice of length {len}"
+            ),
+            Violation::IndexOverflow {
+                indexed: Indexed::Slice { len },
+            } => write!(
+                f,
+                "range end index {} out of range for slice of length {len}",
+                usize::MAX
+            ),
+            Violation::RangeStartOutOfBounds {
+                start,
+                indexed: Indexed::Str(s),
+            } => {
+                let (s_trunc, ellipsis) = truncate(s);
+                write!(
+                    f,
+                    "start byte index {start} is out of bounds of `{s_trunc}`{ellipsis}"
+                )
+            }
+            Violation::RangeEndOutOfBounds {
+                end,
+                indexed: Indexed::Str(s),
+            } => {
+                let (s_trunc, ellipsis) = truncate(s);
+                write!(
+                    f,
+                    "end byte index {end} is out of bounds of `{s_trunc}`{ellipsis}"
+                )
+            }
+            Violation::IndexOverflow {
+                indexed: Indexed::Str(s),
+            } => {
+                let (s_trunc, ellipsis) = truncate(s);
+                write!(
+                    f,
+                    "end byte index {} is out of bounds of `{s_trunc}`{ellipsis}",
+                    usize::MAX
+                )
+            }
+            Violation::RangeInverted {
+                start,
+                end,
+                indexed: Indexed::Slice { .. },
+            } => write!(f, "slice index starts at {start} but ends at {end}"),
+            Violation::RangeInverted {
+                start,
+                end,
+                indexed: Indexed::Str(s),
+            } => {
+                let (s_trunc, ellipsis) = truncate(s);
+                write!(
+                    f,
+                    "begin > end ({start} > {end}) when slicing `{s_trunc}`{ellipsis}"
+                )
+            }
+            Violation::NotCharBoundary {
+                index,
+                is_start,
+                char,
+                char_range,
+                s,
+            } => {
+                let (s_trunc, ellipsis) = truncate(s);
+                let bound = if *is_start { "start" } else { "end" };
+                write!(
+                    f,
+                    "{bound} byte index {index} is not a char boundary; it is inside {char:?} (bytes {char_range:?}) of `{s_trunc}`{ellipsis}"
+                )
+            }
+            Violation::Unreachable => f.write_str("internal error: entered unreachable code"),
+        }
+    }
+}
+
+/// The error returned by [`try_get()`](crate::SliceExt::try_get) and friends when the index is invalid.
+///
+/// Its [`Display`](fmt::Display) implementation produces the same message as its [`Violation`]'s,
+/// i.e. the one the debug checks panic with (without the custom message, if any).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexError<'a> {
+    violation: Violation<'a>,
+}
+
+impl<'a> IndexError<'a> {
+    pub(crate) fn new(violation: Violation<'a>) -> Self {
+        Self { violation }
+    }
+
+    /// Returns the reason why the index is invalid.
+    pub fn violation(&self) -> &Violation<'a> {
+        &self.violation
+    }
+
+    /// Returns the reason why the index is invalid.
+    pub fn into_violation(self) -> Violation<'a> {
+        self.violation
+    }
+}
+
+impl fmt::Display for IndexError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.violation.fmt(f)
+    }
+}
+
+impl core::error::Error for IndexError<'_> {}
+
+/// Truncates the string for display in the error message, like the standard library does.
+fn truncate(s: &str) -> (&str, &'static str) {
+    const MAX_DISPLAY_LENGTH: usize = 256;
+    let trunc_len = crate::str::floor_char_boundary(s, MAX_DISPLAY_LENGTH);
+    let s_trunc = &s[..trunc_len];
+    let ellipsis = if trunc_len < s.len() { "[...]" } else { "" };
+    (s_trunc, ellipsis)
+}
+
+/// Called when the debug checks detect the `violation` of an invariant, with an optional custom error message.
+///
+/// Counts the violation (with the `audit` feature), calls the violation hook and panics
+/// (or aborts the process with the `abort-on-violation` feature, or acts according to the [`CheckPolicy`](crate::CheckPolicy)
+/// with the `runtime-policy` feature).
+#[inline]
+#[track_caller]
+pub(crate) fn violated(violation: Violation<'_>, msg: Option<fmt::Arguments<'_>>) -> ! {
+    #[cfg(feature = "audit")]
+    crate::audit::record_violation(violation.kind(), Location::caller());
+
+    call_violation_hook(&violation, msg, Location::caller());
+
+    #[cfg(feature = "runtime-policy")]
+    match crate::check_policy() {
+        crate::CheckPolicy::Abort => abort(violation, msg),
+        crate::CheckPolicy::Log => {
+            // With the `abort-on-violation` feature `fail()` reports the violation itself.
+            #[cfg(not(feature = "abort-on-violation"))]
+            report(&violation, msg);
+            fail(violation, msg)
+        }
+        crate::CheckPolicy::Off | crate::CheckPolicy::Panic => {}
+    }
+
+    fail(violation, msg)
+}
+
+/// Panics with the description of the violation, like the standard library does for the same errors.
+///
+/// Only reached unreachable code panics like [`unreachable!()`] does, with the "internal error" prefix;
+/// the violations described by the message alone panic with just the message, like [`Option::expect()`] does.
+#[cfg(not(feature = "abort-on-violation"))]
+#[inline]
+#[track_caller]
+fn fail(violation: Violation<'_>, msg: Option<fmt::Arguments<'_>>) -> ! {
+    match (violation, msg) {
+        (Violation::Unreachable, None) => unreachable!(),
+        (Violation::Unreachable, Some(msg)) => unreachable!("{}", msg),
+        (violation, msg) => panic!(
+            "{}",
+            crate::ViolationInfo::new(&violation, msg, Location::caller())
+        ),
+    }
+}
+
+#[cfg(feature = "abort-on-violation")]
+#[inline]
+#[track_caller]
+fn fail(violation: Violation<'_>, msg: Option<fmt::Arguments<'_>>) -> ! {
+    abort(violation, msg)
+}
+
+/// Writes the description of the violation to stderr and aborts the process.
+///
+/// Does not allocate and does not unwind, so is safe to reach e.g. in `extern "C"` callbacks.
+#[cfg(any(feature = "abort-on-violation", feature = "runtime-policy"))]
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn abort(violation: Violation<'_>, msg: Option<fmt::Arguments<'_>>) -> ! {
+    report(&violation, msg);
+    std::process::abort()
+}
+
+/// Writes the description of the violation to stderr, without allocating.
+#[cfg(any(feature = "abort-on-violation", feature = "runtime-policy"))]
+#[track_caller]
+fn report(violation: &Violation<'_>, msg: Option<fmt::Arguments<'_>>) {
+    use std::io::Write;
+
+    let location = Location::caller();
+    let info = crate::ViolationInfo::new(violation, msg, location);
+
+    // `Stderr` is unbuffered, and formatting directly into it does not allocate.
+    let _ = writeln!(
+        std::io::stderr(),
+        "invariant violated at {location}:\n{info}"
+    );
+}
+
+/// Same as [`violated()`], but with an optional custom error message of any [`DbgMessage`](crate::DbgMessage) type,
+/// which is evaluated here.
+#[inline]
+#[track_caller]
+pub(crate) fn violated_msg(violation: Violation<'_>, msg: impl crate::DbgMessage) -> ! {
+    match msg.into_message() {
+        Some(msg) => violated(violation, Some(format_args!("{}", msg))),
+        None => violated(violation, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let do_test = |violation: Violation<'_>, res: &str| {
+            assert_eq!(violation.to_string(), res);
+        };
+
+        do_test(
+            Violation::NoneUnwrapped,
+            "called `Option::unwrap()` on a `None` value",
+        );
+        do_test(
+            Violation::ErrUnwrapped,
+            "called `Result::unwrap()` on an `Err` value",
+        );
+        do_test(
+            Violation::OkUnwrapped,
+            "called `Result::unwrap_err()` on an `Ok` value",
+        );
+        do_test(
+            Violation::NoneExpected,
+            "called `Option::expect()` on a `None` value",
+        );
+        do_test(
+            Violation::ErrExpected,
+            "called `Result::expect()` on an `Err` value",
+        );
+        do_test(
+            Violation::OkExpected,
+            "called `Result::expect_err()` on an `Ok` value",
+        );
+        do_test(
+            Violation::Unwrapped,
+            "called `unwrap()` on a value without an output",
+        );
+        do_test(
+            Violation::IndexOutOfBounds { index: 3, len: 3 },
+            "index out of bounds: the len is 3 but the index is 3",
+        );
+        do_test(
+            Violation::RangeStartOutOfBounds {
+                start: 4,
+                indexed: Indexed::Slice { len: 3 },
+            },
+            "range start index 4 out of range for slice of length 3",
+        );
+        do_test(
+            Violation::RangeEndOutOfBounds {
+                end: 4,
+                indexed: Indexed::Slice { len: 3 },
+            },
+            "range end index 4 out of range for slice of length 3",
+        );
+        do_test(
+            Violation::RangeInverted {
+                start: 2,
+                end: 1,
+                indexed: Indexed::Slice { len: 3 },
+            },
+            "slice index starts at 2 but ends at 1",
+        );
+        do_test(
+            Violation::RangeStartOutOfBounds {
+                start: 5,
+                indexed: Indexed::Str("föo"),
+            },
+            "start byte index 5 is out of bounds of `föo`",
+        );
+        do_test(
+            Violation::RangeEndOutOfBounds {
+                end: 5,
+                indexed: Indexed::Str("föo"),
+            },
+            "end byte index 5 is out of bounds of `föo`",
+        );
+        do_test(
+            Violation::RangeInverted {
+                start: 1,
+                end: 0,
+                indexed: Indexed::Str("föo"),
+            },
+            "begin > end (1 > 0) when slicing `föo`",
+        );
+        do_test(
+            Violation::NotCharBoundary {
+                index: 2,
+                is_start: true,
+                char: 'ö',
+                char_range: 1..3,
+                s: "föo",
+            },
+            "start byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`",
+        );
+        do_test(
+            Violation::NotCharBoundary {
+                index: 2,
+                is_start: false,
+                char: 'ö',
+                char_range: 1..3,
+                s: "föo",
+            },
+            "end byte index 2 is not a char boundary; it is inside 'ö' (bytes 1..3) of `föo`",
+        );
+        do_test(
+            Violation::IndexOverflow {
+                indexed: Indexed::Slice { len: 3 },
+            },
+            "range end index 18446744073709551615 out of range for slice of length 3",
+        );
+        do_test(
+            Violation::IndexOverflow {
+                indexed: Indexed::Str("föo"),
+            },
+            "end byte index 18446744073709551615 is out of bounds of `föo`",
+        );
+        do_test(
+            Violation::Unreachable,
+            "internal error: entered unreachable code",
+        );
+    }
+
+    #[test]
+    fn display_truncated() {
+        let s = "ö".repeat(200);
+        let violation = Violation::RangeEndOutOfBounds {
+            end: 401,
+            indexed: Indexed::Str(&s),
+        };
+        assert_eq!(
+            violation.to_string(),
+            format!(
+                "end byte index 401 is out of bounds of `{}`[...]",
+                "ö".repeat(128)
+            )
+        );
+    }
+}
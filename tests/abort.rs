@@ -0,0 +1,52 @@
+//! Checks that with the `abort-on-violation` feature an invariant violation writes its description to stderr
+//! and aborts the process, by building the crate and a program violating an invariant with `rustc` and running the latter.
+
+mod support;
+
+use {
+    std::process::Command,
+    support::{assert_aborted, build_crate, build_program, out_dir},
+};
+
+#[test]
+fn abort_on_violation() {
+    let out_dir = out_dir("abort");
+    let extern_crate = build_crate(
+        &out_dir,
+        &[
+            "--cfg",
+            "feature=\"std\"",
+            "--cfg",
+            "feature=\"abort-on-violation\"",
+            // Emitted by the build script when the checks are enabled, which does not run here.
+            "--cfg",
+            "miniunchecked_checks_enabled",
+        ],
+    );
+
+    let program = build_program(
+        &out_dir,
+        &extern_crate,
+        r#"
+use miniunchecked::SliceExt;
+
+fn main() {
+    let slice = [2, 3, 4];
+    let index = std::hint::black_box(3);
+    let _ = unsafe { slice.get_unchecked_dbg_msg(index, "invalid index") };
+    println!("unreachable");
+}
+"#,
+    );
+    let output = Command::new(program).output().unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr,
+        "invariant violated at <anon>:7:28:\n\
+         index out of bounds: the len is 3 but the index is 3: invalid index\n"
+    );
+    assert!(output.stdout.is_empty());
+
+    assert_aborted(&output);
+}
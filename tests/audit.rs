@@ -0,0 +1,64 @@
+//! Checks that with the `audit` and `abort-on-violation` features an invariant violation is counted
+//! and then aborts the process like without the `audit` feature, by building the crate and a program
+//! violating an invariant with `rustc` and running the latter.
+
+mod support;
+
+use {
+    std::process::Command,
+    support::{assert_aborted, build_crate, build_program, out_dir},
+};
+
+#[test]
+fn audit_abort_on_violation() {
+    let out_dir = out_dir("audit");
+    let extern_crate = build_crate(
+        &out_dir,
+        &[
+            "--cfg",
+            "feature=\"std\"",
+            "--cfg",
+            "feature=\"audit\"",
+            "--cfg",
+            "feature=\"abort-on-violation\"",
+            // Emitted by the build script when the checks are enabled, which does not run here.
+            "--cfg",
+            "miniunchecked_checks_enabled",
+        ],
+    );
+
+    let program = build_program(
+        &out_dir,
+        &extern_crate,
+        r#"
+use miniunchecked::*;
+
+fn main() {
+    // The hook is called after the violation is counted.
+    set_violation_hook(|_| {
+        for counter in audit_counters() {
+            println!("{}: {:?} x{}", counter.location(), counter.kind(), counter.count());
+        }
+    });
+
+    let slice = [2, 3, 4];
+    let index = std::hint::black_box(3);
+    let _ = std::panic::catch_unwind(|| unsafe { *slice.get_unchecked_dbg(index) });
+    println!("unreachable");
+}
+"#,
+    );
+    let output = Command::new(program).output().unwrap();
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "<anon>:14:57: IndexOutOfBounds x1\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "invariant violated at <anon>:14:57:\n\
+         index out of bounds: the len is 3 but the index is 3\n"
+    );
+
+    assert_aborted(&output);
+}
@@ -0,0 +1,151 @@
+//! Checks that in release configuration the extension trait methods compile to exactly the same code
+//! as the standard library unchecked operations they replace.
+//!
+//! Builds the crate and `tests/codegen/unchecked.rs` with `rustc` (without `debug_assertions`)
+//! and compares the optimized LLVM IR of each `dbg_*` function in the latter to its `std_*` counterpart.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[test]
+fn release_codegen_matches_std() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    rustc(&[
+        "--crate-type=rlib",
+        "--crate-name=miniunchecked",
+        manifest_dir.join("src/lib.rs").to_str().unwrap(),
+        "--out-dir",
+        out_dir.to_str().unwrap(),
+    ]);
+
+    rustc(&[
+        "--crate-type=lib",
+        "--crate-name=unchecked",
+        "--emit=llvm-ir",
+        manifest_dir
+            .join("tests/codegen/unchecked.rs")
+            .to_str()
+            .unwrap(),
+        "--extern",
+        &format!(
+            "miniunchecked={}",
+            out_dir.join("libminiunchecked.rlib").display()
+        ),
+        "--out-dir",
+        out_dir.to_str().unwrap(),
+    ]);
+
+    let ir = fs::read_to_string(out_dir.join("unchecked.ll")).unwrap();
+    let functions = functions(&ir);
+
+    let mut compared = 0;
+
+    for (name, body) in &functions {
+        let Some(name) = name.strip_prefix("dbg_") else {
+            continue;
+        };
+        let std_body = functions
+            .get(&format!("std_{name}"))
+            .unwrap_or_else(|| panic!("missing `std_{name}`"));
+
+        assert_eq!(
+            body, std_body,
+            "`dbg_{name}` does not compile to the same code as `std_{name}`"
+        );
+        compared += 1;
+    }
+
+    assert_eq!(compared * 2, functions.len());
+    assert!(compared > 0);
+}
+
+fn rustc(args: &[&str]) {
+    let output = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+        .args([
+            "--edition=2024",
+            "-Copt-level=3",
+            "-Cdebug-assertions=off",
+            "-Ccodegen-units=1",
+        ])
+        .args(args)
+        .output()
+        .expect("failed to run `rustc`");
+
+    assert!(
+        output.status.success(),
+        "`rustc` failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Returns the bodies of all `dbg_*` / `std_*` functions in the LLVM IR, by name,
+/// with the attribute group and metadata references (which may differ between identical functions) removed.
+///
+/// LLVM may merge identical functions, replacing all but one with aliases - these are resolved to the aliased function's body.
+fn functions(ir: &str) -> BTreeMap<String, String> {
+    let is_compared = |name: &&str| name.starts_with("dbg_") || name.starts_with("std_");
+
+    let mut functions = BTreeMap::new();
+    let mut aliases = Vec::new();
+    let mut lines = ir.lines();
+
+    while let Some(line) = lines.next() {
+        // E.g. `@std_option = unnamed_addr alias i32 (i32, i32), ptr @dbg_option`.
+        if let Some((name, aliased)) = line
+            .strip_prefix('@')
+            .and_then(|line| line.split_once(" = "))
+            .filter(|(_, line)| line.contains(" alias "))
+            .and_then(|(name, line)| Some((name, line.rsplit_once(" @")?.1)))
+            .filter(|(name, _)| is_compared(name))
+        {
+            aliases.push((name.to_string(), aliased.to_string()));
+            continue;
+        }
+
+        let Some(name) = line
+            .strip_prefix("define ")
+            .and_then(|line| line.split_once(" @"))
+            .and_then(|(_, line)| line.split_once('('))
+            .map(|(name, _)| name)
+            .filter(is_compared)
+        else {
+            continue;
+        };
+
+        let body = lines
+            .by_ref()
+            .take_while(|line| *line != "}")
+            .map(strip_references)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        functions.insert(name.to_string(), body);
+    }
+
+    for (name, aliased) in aliases {
+        let body = functions
+            .get(&aliased)
+            .unwrap_or_else(|| panic!("`{name}` aliases unknown function `{aliased}`"))
+            .clone();
+        functions.insert(name, body);
+    }
+
+    functions
+}
+
+fn strip_references(line: &str) -> String {
+    line.split(", !")
+        .next()
+        .unwrap()
+        .split(' ')
+        .filter(|token| !token.starts_with('#') && !token.starts_with('!'))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
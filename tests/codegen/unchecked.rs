@@ -0,0 +1,444 @@
+//! Compiled by `tests/codegen.rs`: each `dbg_*` function must compile to the same code as its `std_*` counterpart
+//! in release configuration.
+
+use {
+    core::{
+        hint::unreachable_unchecked,
+        ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive},
+    },
+    miniunchecked::*,
+};
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_usize(s: &[u32], i: usize) -> &u32 {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_usize(s: &[u32], i: usize) -> &u32 {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_usize_mut(s: &mut [u32], i: usize) -> &mut u32 {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_usize_mut(s: &mut [u32], i: usize) -> &mut u32 {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range(s: &[u32], i: Range<usize>) -> &[u32] {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range(s: &[u32], i: Range<usize>) -> &[u32] {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_mut(s: &mut [u32], i: Range<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_mut(s: &mut [u32], i: Range<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_from(s: &[u32], i: RangeFrom<usize>) -> &[u32] {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_from(s: &[u32], i: RangeFrom<usize>) -> &[u32] {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_from_mut(s: &mut [u32], i: RangeFrom<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_from_mut(s: &mut [u32], i: RangeFrom<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_to(s: &[u32], i: RangeTo<usize>) -> &[u32] {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_to(s: &[u32], i: RangeTo<usize>) -> &[u32] {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_to_mut(s: &mut [u32], i: RangeTo<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_to_mut(s: &mut [u32], i: RangeTo<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_inclusive(s: &[u32], i: RangeInclusive<usize>) -> &[u32] {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_inclusive(s: &[u32], i: RangeInclusive<usize>) -> &[u32] {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_inclusive_mut(s: &mut [u32], i: RangeInclusive<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_inclusive_mut(s: &mut [u32], i: RangeInclusive<usize>) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_to_inclusive(s: &[u32], i: RangeToInclusive<usize>) -> &[u32] {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_to_inclusive(s: &[u32], i: RangeToInclusive<usize>) -> &[u32] {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_range_to_inclusive_mut(
+    s: &mut [u32],
+    i: RangeToInclusive<usize>,
+) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_range_to_inclusive_mut(
+    s: &mut [u32],
+    i: RangeToInclusive<usize>,
+) -> &mut [u32] {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range(s: &str, i: Range<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range(s: &str, i: Range<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_mut(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_mut(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_from(s: &str, i: RangeFrom<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_from(s: &str, i: RangeFrom<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_from_mut(s: &mut str, i: RangeFrom<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_from_mut(s: &mut str, i: RangeFrom<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_to(s: &str, i: RangeTo<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_to(s: &str, i: RangeTo<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_to_mut(s: &mut str, i: RangeTo<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_to_mut(s: &mut str, i: RangeTo<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_inclusive(s: &str, i: RangeInclusive<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_inclusive(s: &str, i: RangeInclusive<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_inclusive_mut(s: &mut str, i: RangeInclusive<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_inclusive_mut(s: &mut str, i: RangeInclusive<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_to_inclusive(s: &str, i: RangeToInclusive<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_to_inclusive(s: &str, i: RangeToInclusive<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_to_inclusive_mut(s: &mut str, i: RangeToInclusive<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_to_inclusive_mut(s: &mut str, i: RangeToInclusive<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_usize_fmt(s: &[u32], i: usize) -> &u32 {
+    unsafe { s.get_unchecked_dbg_fmt(i, format_args!("invalid index {i}")) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_usize_fmt(s: &[u32], i: usize) -> &u32 {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_slice_usize_mut_with(s: &mut [u32], i: usize) -> &mut u32 {
+    unsafe { s.get_unchecked_mut_dbg_with(i, || i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_slice_usize_mut_with(s: &mut [u32], i: usize) -> &mut u32 {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_fmt(s: &str, i: Range<usize>) -> &str {
+    unsafe { s.get_unchecked_dbg_fmt(i.clone(), format_args!("invalid range {i:?}")) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_fmt(s: &str, i: Range<usize>) -> &str {
+    unsafe { s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_str_range_mut_with(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut_dbg_with(i.clone(), || i.start) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_str_range_mut_with(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_index_nested(g: &[[u32; 4]], y: usize, x: usize) -> u32 {
+    unsafe { index_dbg!(g[y][x]) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_index_nested(g: &[[u32; 4]], y: usize, x: usize) -> u32 {
+    unsafe { *g.get_unchecked(y).get_unchecked(x) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_wrapper_slice(s: &[u32], i: usize) -> u32 {
+    unsafe { s.dbg()[i] }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_wrapper_slice(s: &[u32], i: usize) -> u32 {
+    unsafe { *s.get_unchecked(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_wrapper_str_mut(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { &mut s.dbg_mut()[i] }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_wrapper_str_mut(s: &mut str, i: Range<usize>) -> &mut str {
+    unsafe { s.get_unchecked_mut(i) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_option(o: Option<u32>) -> u32 {
+    unsafe { o.unwrap_unchecked_dbg() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_option(o: Option<u32>) -> u32 {
+    unsafe { o.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_option_msg(o: Option<u32>) -> u32 {
+    unsafe { o.unwrap_unchecked_dbg_msg("missing value") }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_option_msg(o: Option<u32>) -> u32 {
+    unsafe { o.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked_dbg() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_msg(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked_dbg_msg("missing value") }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_msg(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_debug(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_debug_unchecked_dbg() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_debug(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_macro(r: Result<u32, u64>) -> u32 {
+    unsafe { unwrap_unchecked_dbg!(r, "missing value") }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_macro(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_err(r: Result<u32, u64>) -> u64 {
+    unsafe { r.unwrap_err_unchecked_dbg() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_err(r: Result<u32, u64>) -> u64 {
+    unsafe { r.unwrap_err_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_err_debug(r: Result<u32, u64>) -> u64 {
+    unsafe { r.unwrap_err_debug_unchecked_dbg() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_err_debug(r: Result<u32, u64>) -> u64 {
+    unsafe { r.unwrap_err_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_result_expect(r: Result<u32, u64>) -> u32 {
+    unsafe { r.expect_unchecked_dbg("missing value") }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_result_expect(r: Result<u32, u64>) -> u32 {
+    unsafe { r.unwrap_unchecked() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_poll(p: core::task::Poll<u32>) -> u32 {
+    unsafe { unwrap_unchecked_dbg!(p) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_poll(p: core::task::Poll<u32>) -> u32 {
+    match p {
+        core::task::Poll::Ready(val) => val,
+        core::task::Poll::Pending => unsafe { unreachable_unchecked() },
+    }
+}
+
+#[derive(Debug)]
+pub enum Shape {
+    Circle { r: u32 },
+    Rect { w: u32, h: u32 },
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_variant(s: &Shape) -> u32 {
+    unsafe { unwrap_variant_dbg!(s, Shape::Rect { w, h } => w * h) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_variant(s: &Shape) -> u32 {
+    match s {
+        Shape::Rect { w, h } => w * h,
+        Shape::Circle { .. } => unsafe { unreachable_unchecked() },
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn dbg_unreachable(x: u32) -> u32 {
+    if x > 7 {
+        unsafe { unreachable_dbg() }
+    }
+    x
+}
+
+#[unsafe(no_mangle)]
+pub unsafe fn std_unreachable(x: u32) -> u32 {
+    if x > 7 {
+        unsafe { unreachable_unchecked() }
+    }
+    x
+}
@@ -0,0 +1,178 @@
+//! Checks the precedence of the settings selecting [`CHECKS_ENABLED`](miniunchecked::CHECKS_ENABLED),
+//! by running the build script, which resolves them, with the environment cargo would set in different configurations.
+
+mod support;
+
+use {
+    std::{path::PathBuf, process::Command, sync::OnceLock},
+    support::{assert_success, build_crate, out_dir, rustc},
+};
+
+/// Builds the build script (once), returning the path of the executable.
+fn build_script() -> &'static PathBuf {
+    static BUILD_SCRIPT: OnceLock<PathBuf> = OnceLock::new();
+
+    BUILD_SCRIPT.get_or_init(|| {
+        let out_dir = out_dir("config/build_script");
+        let output = rustc(
+            &[
+                "--crate-type=bin",
+                "--crate-name=build_script",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/build.rs"),
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+            ],
+            "",
+        );
+        assert_success(&output);
+        out_dir.join("build_script")
+    })
+}
+
+/// Runs the build script with the environment variables `env` and checks that `CHECKS_ENABLED` is `expected`.
+fn check(env: &[(&str, &str)], expected: bool) {
+    let output = Command::new(build_script())
+        .env_clear()
+        .envs(env.iter().copied())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let checks_enabled = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .any(|line| line == "cargo:rustc-cfg=miniunchecked_checks_enabled");
+    assert_eq!(
+        checks_enabled, expected,
+        "`CHECKS_ENABLED` is not `{expected}` with {env:?}"
+    );
+}
+
+const DEBUG: (&str, &str) = ("CARGO_CFG_DEBUG_ASSERTIONS", "");
+const ALWAYS_CHECK: (&str, &str) = ("CARGO_FEATURE_ALWAYS_CHECK", "1");
+const NEVER_CHECK: (&str, &str) = ("CARGO_FEATURE_NEVER_CHECK", "1");
+const UB_CHECKS: (&str, &str) = ("CARGO_FEATURE_UB_CHECKS", "1");
+
+/// `--cfg miniunchecked_checks="{value}"`.
+fn checks_cfg(value: &str) -> (&str, &str) {
+    ("CARGO_CFG_MINIUNCHECKED_CHECKS", value)
+}
+
+/// `RUSTFLAGS`, separated like in `CARGO_ENCODED_RUSTFLAGS`.
+fn rustflags(flags: &str) -> (&str, &str) {
+    ("CARGO_ENCODED_RUSTFLAGS", flags)
+}
+
+#[test]
+fn debug_assertions() {
+    check(&[DEBUG], true);
+    check(&[], false);
+    check(&[checks_cfg("auto")], false);
+}
+
+#[test]
+fn features() {
+    check(&[ALWAYS_CHECK], true);
+    check(&[DEBUG, NEVER_CHECK], false);
+    check(&[checks_cfg("auto"), ALWAYS_CHECK], true);
+}
+
+#[test]
+fn never_check_overrides_always_check() {
+    // E.g. with `--all-features`.
+    check(&[ALWAYS_CHECK, NEVER_CHECK], false);
+    check(&[DEBUG, ALWAYS_CHECK, NEVER_CHECK, UB_CHECKS], false);
+}
+
+#[test]
+fn ub_checks() {
+    check(&[UB_CHECKS], false);
+    check(&[DEBUG, UB_CHECKS], true);
+    check(&[UB_CHECKS, rustflags("-Zub-checks")], true);
+    check(&[UB_CHECKS, rustflags("-Z\x1fub-checks=yes")], true);
+    check(&[DEBUG, UB_CHECKS, rustflags("-Zub-checks=no")], false);
+    check(
+        &[DEBUG, checks_cfg("ub_checks"), rustflags("-Zub-checks=off")],
+        false,
+    );
+    check(&[ALWAYS_CHECK, checks_cfg("ub_checks")], false);
+}
+
+#[test]
+fn cfg_overrides_features() {
+    check(&[checks_cfg("on"), NEVER_CHECK], true);
+    check(&[DEBUG, checks_cfg("off"), ALWAYS_CHECK], false);
+}
+
+#[test]
+fn instrumented_builds() {
+    check(&[("CARGO_CFG_FUZZING", ""), NEVER_CHECK], true);
+    check(&[("CARGO_CFG_MIRI", ""), NEVER_CHECK], true);
+    check(&[rustflags("-Zsanitizer=address"), NEVER_CHECK], true);
+    check(&[rustflags("-Z\x1fsanitizer=address")], true);
+    check(
+        &[DEBUG, ("CARGO_CFG_FUZZING", ""), checks_cfg("off")],
+        false,
+    );
+}
+
+#[test]
+fn checks_enabled_cfg() {
+    let out_dir = out_dir("config/checks_enabled");
+    let check = |flags: &[&str], expected: bool| {
+        let extern_crate = build_crate(&out_dir, flags);
+        let output = rustc(
+            &[
+                "--crate-type=lib",
+                "--crate-name=config",
+                "--emit=metadata",
+                "--extern",
+                &extern_crate,
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+                "-",
+            ],
+            &format!("const _: () = assert!(miniunchecked::CHECKS_ENABLED == {expected});"),
+        );
+        assert_success(&output);
+    };
+
+    // `CHECKS_ENABLED` follows the build script's cfg alone.
+    check(&["--cfg", "miniunchecked_checks_enabled"], true);
+    check(&["-Cdebug-assertions=on"], false);
+}
+
+#[test]
+fn duplicate_cfg() {
+    let out_dir = out_dir("config/duplicate");
+    let output = rustc(
+        &[
+            "--crate-type=rlib",
+            "--crate-name=miniunchecked",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs"),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--cfg",
+            "miniunchecked_checks=\"auto\"",
+            "--cfg",
+            "miniunchecked_checks=\"on\"",
+        ],
+        "",
+    );
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("`--cfg miniunchecked_checks` may only be specified once")
+    );
+
+    // Specifying the same value twice is harmless.
+    build_crate(
+        &out_dir,
+        &[
+            "--cfg",
+            "miniunchecked_checks=\"on\"",
+            "--cfg",
+            "miniunchecked_checks=\"on\"",
+        ],
+    );
+}
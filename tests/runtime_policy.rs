@@ -0,0 +1,150 @@
+//! Checks the behavior of each `CheckPolicy` with the `runtime-policy` feature, by building the crate
+//! and a program violating an invariant with `rustc` and running the latter with different `MINIUNCHECKED` values.
+
+mod support;
+
+use {
+    std::process::{Command, Output},
+    support::{assert_aborted, build_crate, build_program, out_dir},
+};
+
+const PROGRAM: &str = r#"
+use miniunchecked::*;
+
+fn main() {
+    // `set_check_policy()` overrides `MINIUNCHECKED`.
+    match std::env::args().nth(1).as_deref() {
+        Some("panic") => set_check_policy(CheckPolicy::Panic),
+        Some("log") => set_check_policy(CheckPolicy::Log),
+        _ => {}
+    }
+    println!("{:?}", check_policy());
+
+    let slice = [2, 3, 4];
+
+    if check_policy() == CheckPolicy::Off {
+        // The unchecked operations are used, so only valid ones may be performed.
+        assert_eq!(unsafe { *slice.get_unchecked_dbg(std::hint::black_box(2)) }, 4);
+        assert_eq!(unsafe { Some(7).unwrap_unchecked_dbg() }, 7);
+        return;
+    }
+
+    let result = std::panic::catch_unwind(|| unsafe {
+        *slice.get_unchecked_dbg(std::hint::black_box(3))
+    });
+    println!("panicked: {}", result.is_err());
+}
+"#;
+
+const MESSAGE: &str = "index out of bounds: the len is 3 but the index is 3";
+
+#[test]
+fn check_policies() {
+    let out_dir = out_dir("runtime_policy");
+    let extern_crate = build_crate(
+        &out_dir,
+        &[
+            "--cfg",
+            "feature=\"std\"",
+            "--cfg",
+            "feature=\"runtime-policy\"",
+            // Emitted by the build script when the checks are enabled, which does not run here.
+            "--cfg",
+            "miniunchecked_checks_enabled",
+        ],
+    );
+    let program = build_program(&out_dir, &extern_crate, PROGRAM);
+
+    let run = |env: Option<&str>, args: &[&str]| -> Output {
+        let mut command = Command::new(&program);
+        command
+            .args(args)
+            .env_remove("MINIUNCHECKED")
+            .env_remove("RUST_BACKTRACE");
+        if let Some(env) = env {
+            command.env("MINIUNCHECKED", env);
+        }
+        command.output().unwrap()
+    };
+
+    // `CheckPolicy::DEFAULT` - `Panic`, as the checks are enabled.
+    let output = run(None, &[]);
+    assert_eq!(stdout(&output), "Panic\npanicked: true\n");
+    assert!(stderr(&output).contains(&format!("panicked at <anon>:23:16:\n{MESSAGE}\n")));
+
+    let output = run(Some("off"), &[]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Off\n");
+    assert_eq!(stderr(&output), "");
+
+    let output = run(Some("PANIC"), &[]);
+    assert_eq!(stdout(&output), "Panic\npanicked: true\n");
+    assert!(!stderr(&output).contains("invariant violated"));
+    assert!(stderr(&output).contains(&format!("panicked at <anon>:23:16:\n{MESSAGE}\n")));
+
+    // The violation is logged, and then panics like with `Panic`.
+    let output = run(Some("log"), &[]);
+    assert_eq!(stdout(&output), "Log\npanicked: true\n");
+    assert_logged(&output);
+
+    let output = run(Some("abort"), &[]);
+    assert_eq!(stdout(&output), "Abort\n");
+    assert_eq!(
+        stderr(&output),
+        format!("invariant violated at <anon>:23:16:\n{MESSAGE}\n")
+    );
+    assert_aborted(&output);
+
+    // `set_check_policy()` overrides the environment variable.
+    let output = run(Some("off"), &["panic"]);
+    assert_eq!(stdout(&output), "Panic\npanicked: true\n");
+    let output = run(Some("abort"), &["log"]);
+    assert_eq!(stdout(&output), "Log\npanicked: true\n");
+    assert_logged(&output);
+}
+
+#[test]
+fn log_abort_on_violation() {
+    let out_dir = out_dir("runtime_policy_abort");
+    let extern_crate = build_crate(
+        &out_dir,
+        &[
+            "--cfg",
+            "feature=\"std\"",
+            "--cfg",
+            "feature=\"runtime-policy\"",
+            "--cfg",
+            "feature=\"abort-on-violation\"",
+            // Emitted by the build script when the checks are enabled, which does not run here.
+            "--cfg",
+            "miniunchecked_checks_enabled",
+        ],
+    );
+    let program = build_program(&out_dir, &extern_crate, PROGRAM);
+
+    // The violation is logged (once) and then aborts the process instead of panicking.
+    let output = Command::new(&program)
+        .env("MINIUNCHECKED", "log")
+        .output()
+        .unwrap();
+    assert_eq!(stdout(&output), "Log\n");
+    assert_eq!(
+        stderr(&output),
+        format!("invariant violated at <anon>:23:16:\n{MESSAGE}\n")
+    );
+    assert_aborted(&output);
+}
+
+fn assert_logged(output: &Output) {
+    let stderr = stderr(output);
+    assert!(stderr.starts_with(&format!("invariant violated at <anon>:23:16:\n{MESSAGE}\n")));
+    assert!(stderr.contains(&format!("panicked at <anon>:23:16:\n{MESSAGE}\n")));
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,115 @@
+//! Helpers for the tests which build the crate and test programs using it with `rustc`,
+//! e.g. to test different configurations or the behavior of the process.
+
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+/// Returns a fresh output directory named `name` for the build artifacts.
+pub fn out_dir(name: &str) -> PathBuf {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&out_dir).unwrap();
+    out_dir
+}
+
+/// Builds the crate with the additional `rustc` `flags` (e.g. `--cfg feature="std"`) into `out_dir`,
+/// returning the `--extern` flag linking to it.
+///
+/// The build script does not run, so the flags must include its `--cfg miniunchecked_checks_enabled`
+/// for the checks to be enabled.
+pub fn build_crate(out_dir: &Path, flags: &[&str]) -> String {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let output = rustc(
+        &[
+            &[
+                "--crate-type=rlib",
+                "--crate-name=miniunchecked",
+                manifest_dir.join("src/lib.rs").to_str().unwrap(),
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+            ],
+            flags,
+        ]
+        .concat(),
+        "",
+    );
+    assert_success(&output);
+
+    format!(
+        "miniunchecked={}",
+        out_dir.join("libminiunchecked.rlib").display()
+    )
+}
+
+/// Builds the program `source` (read from stdin) with the crate linked via `extern_crate` (see [`build_crate()`])
+/// into `out_dir`, returning the path of the executable.
+pub fn build_program(out_dir: &Path, extern_crate: &str, source: &str) -> PathBuf {
+    let output = rustc(
+        &[
+            "--crate-type=bin",
+            "--crate-name=program",
+            "--extern",
+            extern_crate,
+            "-L",
+            out_dir.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "-",
+        ],
+        source,
+    );
+    assert_success(&output);
+
+    out_dir.join("program")
+}
+
+/// Runs `rustc` with `args`, passing `stdin` to it.
+pub fn rustc(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+        .arg("--edition=2024")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run `rustc`");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+pub fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "`rustc` failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Asserts that the process was aborted (via `std::process::abort()`), rather than exited, e.g. after a panic.
+pub fn assert_aborted(output: &Output) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        // `SIGABRT`, rather than the exit code 101 of a panic.
+        assert_eq!(output.status.signal(), Some(6));
+    }
+
+    #[cfg(not(unix))]
+    assert!(!output.status.success() && output.status.code() != Some(101));
+}
@@ -0,0 +1,273 @@
+//! Tests of the `#[unchecked_dbg]` attribute macro, which live here as a proc-macro crate can't use its own macros.
+
+use {miniunchecked_macros::unchecked_dbg, std::collections::HashMap};
+
+struct Grid {
+    cells: Vec<Vec<i32>>,
+}
+
+#[unchecked_dbg]
+unsafe fn cell(grid: &Grid, x: usize, y: usize) -> i32 {
+    grid.cells[y][x]
+}
+
+#[unchecked_dbg]
+unsafe fn set_cell(grid: &mut Grid, x: usize, y: usize, value: i32) {
+    grid.cells[y][x] = value;
+}
+
+#[unchecked_dbg]
+unsafe fn row(grid: &Grid, y: usize, xs: std::ops::Range<usize>) -> &[i32] {
+    &grid.cells[y][xs]
+}
+
+#[unchecked_dbg]
+unsafe fn parse_prefix(s: &str, len: usize) -> u32 {
+    s[..len].parse().unwrap()
+}
+
+#[unchecked_dbg]
+unsafe fn lookup(map: &HashMap<&str, Vec<u8>>, key: &str, i: usize) -> u8 {
+    map.get(key).unwrap()[i]
+}
+
+#[unchecked_dbg]
+unsafe fn digit(byte: u8) -> u8 {
+    match byte {
+        b'0'..=b'9' => byte - b'0',
+        _ => unreachable!("not a digit: {byte}"),
+    }
+}
+
+#[unchecked_dbg]
+unsafe fn last(values: &[i32]) -> i32 {
+    match values.len() {
+        0 => unreachable!(),
+        len => values[len - 1],
+    }
+}
+
+#[unchecked_dbg]
+fn increment_all(values: &mut [i32], indices: &[usize]) {
+    // SAFETY: the indices are in bounds.
+    unsafe {
+        for &i in indices {
+            values[i] += 1;
+            values[indices[0]] -= 1;
+        }
+    }
+}
+
+#[unchecked_dbg]
+fn uppercase_middle(strings: &mut [String]) {
+    // SAFETY: the strings are ASCII and at least 3 bytes long.
+    unsafe {
+        let middle = &mut strings[0][1..2];
+        middle.make_ascii_uppercase();
+        (&mut strings[1][1..]).make_ascii_uppercase();
+        let _ = &raw mut strings[1];
+    }
+}
+
+#[unchecked_dbg]
+unsafe fn sum_with(values: &[i32], indices: &[usize]) -> i32 {
+    // Tuples and macro arguments are rewritten too, closures are not.
+    let pair = (values[indices[0]], [values[indices[1]]][0]);
+    let get = |i: usize| values[indices[i]];
+    assert_eq!(get(0), pair.0, "{}", values[0]);
+    pair.0 + pair.1 + get(2)
+}
+
+#[unchecked_dbg(crate = ::miniunchecked)]
+unsafe fn first<T: Copy>(values: &[T]) -> T {
+    let checked = values.first().copied();
+    let _: Vec<[u8; 2]> = Vec::<[u8; 2]>::new();
+    checked.ok_or(()).unwrap()
+}
+
+#[unchecked_dbg]
+unsafe fn getter(v: Vec<u64>) -> impl Fn(usize) -> u64 {
+    move |i| v[i]
+}
+
+#[unchecked_dbg]
+unsafe fn first_byte(bytes: &[u8]) -> u8 {
+    const FIRST: usize = [0][0];
+
+    fn helper(x: &[u8]) -> u8 {
+        x[0]
+    }
+
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Bytes<'_> {
+        fn first(&self) -> u8 {
+            self.0[FIRST]
+        }
+    }
+
+    let first = async move { bytes[0] };
+    drop(first);
+    assert_eq!(helper(bytes), Bytes(bytes).first());
+    bytes[FIRST]
+}
+
+#[test]
+fn success() {
+    let mut grid = Grid {
+        cells: vec![vec![1, 2, 3], vec![4, 5, 6]],
+    };
+
+    unsafe {
+        assert_eq!(cell(&grid, 2, 1), 6);
+        set_cell(&mut grid, 2, 1, 7);
+        assert_eq!(cell(&grid, 2, 1), 7);
+        assert_eq!(row(&grid, 0, 1..3), [2, 3]);
+
+        assert_eq!(parse_prefix("12x", 2), 12);
+        assert_eq!(lookup(&HashMap::from([("a", vec![1, 2])]), "a", 1), 2);
+        assert_eq!(digit(b'7'), 7);
+        assert_eq!(last(&[1, 2, 3]), 3);
+        assert_eq!(sum_with(&[1, 2, 3], &[0, 2, 1]), 6);
+        assert_eq!(first(&[4, 5]), 4);
+    }
+
+    let mut values = [1, 2, 3];
+    increment_all(&mut values, &[1, 2]);
+    assert_eq!(values, [1, 1, 4]);
+
+    let mut strings = vec!["abc".to_string(), "def".to_string()];
+    uppercase_middle(&mut strings);
+    assert_eq!(strings, ["aBc", "dEF"]);
+}
+
+// Not gated on the checks: the closures and nested items are left checked, so they panic like std even in release configuration.
+#[test]
+fn closures_and_nested_items_failure() {
+    let getter = unsafe { getter(vec![1, 2]) };
+    assert_eq!(getter(1), 2);
+    assert_eq!(
+        panic_message(|| getter(2)),
+        "index out of bounds: the len is 2 but the index is 2"
+    );
+
+    assert_eq!(unsafe { first_byte(&[3, 4]) }, 3);
+    assert_eq!(
+        panic_message(|| unsafe { first_byte(&[]) }),
+        "index out of bounds: the len is 0 but the index is 0"
+    );
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "index out of bounds: the len is 3 but the index is 3: in `grid.cells[y][x]`"]
+fn index_failure() {
+    let grid = Grid {
+        cells: vec![vec![1, 2, 3], vec![4, 5, 6]],
+    };
+    unsafe { cell(&grid, 3, 1) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "index out of bounds: the len is 2 but the index is 2: in `grid.cells[y]`"]
+fn index_mut_failure() {
+    let mut grid = Grid {
+        cells: vec![vec![1, 2, 3], vec![4, 5, 6]],
+    };
+    unsafe { set_cell(&mut grid, 0, 2, 7) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "range end index 4 out of range for slice of length 3: in `grid.cells[y][xs]`"]
+fn index_range_failure() {
+    let grid = Grid {
+        cells: vec![vec![1, 2, 3], vec![4, 5, 6]],
+    };
+    unsafe { row(&grid, 0, 1..4) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "end byte index 4 is out of bounds of `12x`: in `s[..len]`"]
+fn index_str_failure() {
+    unsafe { parse_prefix("12x", 4) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }"]
+fn unwrap_failure() {
+    unsafe { parse_prefix("12x", 3) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "called `Option::unwrap()` on a `None` value"]
+fn unwrap_chain_failure() {
+    unsafe { lookup(&HashMap::from([("a", vec![1, 2])]), "b", 1) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "internal error: entered unreachable code: not a digit: 120"]
+fn unreachable_failure() {
+    unsafe { digit(b'x') };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "internal error: entered unreachable code"]
+fn unreachable_no_message_failure() {
+    unsafe { last(&[]) };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "index out of bounds: the len is 3 but the index is 5: in `values[i]`"]
+fn unsafe_block_failure() {
+    increment_all(&mut [1, 2, 3], &[5]);
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+fn location() {
+    use std::{panic, sync::mpsc};
+
+    let (sender, receiver) = mpsc::channel();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info.location().unwrap();
+        sender
+            .send((location.file().to_string(), location.line()))
+            .unwrap();
+    }));
+
+    let grid = Grid {
+        cells: vec![vec![1, 2, 3]],
+    };
+    let results = [
+        panic::catch_unwind(|| unsafe { cell(&grid, 3, 0) }).is_err(),
+        panic::catch_unwind(|| unsafe { parse_prefix("x", 1) }).is_err(),
+        panic::catch_unwind(|| unsafe { digit(b'x') }).is_err(),
+    ];
+    panic::set_hook(default_hook);
+
+    assert_eq!(results, [true; 3]);
+    let file = file!().to_string();
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        [(file.clone(), 11), (file.clone(), 26), (file, 38)]
+    );
+}
+
+/// Returns the message `f` panics with.
+fn panic_message<R>(f: impl FnOnce() -> R) -> String {
+    let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .err()
+        .expect("expected a panic");
+    *payload
+        .downcast::<String>()
+        .expect("the panic payload is not a `String`")
+}
@@ -0,0 +1,98 @@
+//! Tests of the `#[derive(UncheckedVariants)]` derive macro, which live here as a proc-macro crate can't use its own macros.
+
+use miniunchecked_macros::UncheckedVariants;
+
+#[derive(Debug, PartialEq, UncheckedVariants)]
+enum Shape {
+    Circle { r: u32 },
+    Rect { w: u32, h: u32 },
+    Point,
+}
+
+#[derive(UncheckedVariants)]
+#[unchecked_variants(crate = ::miniunchecked)]
+pub(crate) enum Node<'a, T: Iterator<Item = u8> = std::vec::IntoIter<u8>, const N: usize = 2>
+where
+    T: Clone,
+{
+    Leaf(&'a str),
+    Bytes(T, [u8; N]),
+    HTTPError(Box<dyn Fn(u8) -> Vec<u8>>),
+    Empty(),
+}
+
+#[test]
+fn success() {
+    let mut shape = Shape::Rect { w: 2, h: 3 };
+
+    unsafe {
+        assert_eq!(shape.as_rect_unchecked_dbg(), (&2, &3));
+        *shape.as_rect_mut_unchecked_dbg().0 = 4;
+        assert_eq!(shape.into_rect_unchecked_dbg(), (4, 3));
+
+        assert_eq!(*Shape::Circle { r: 7 }.as_circle_unchecked_dbg(), 7);
+        Shape::Point.as_point_unchecked_dbg();
+    }
+
+    let node: Node<'_> = Node::Bytes(vec![1, 2].into_iter(), [3, 4]);
+    let (bytes, array) = unsafe { node.into_bytes_unchecked_dbg() };
+    assert_eq!((bytes.collect::<Vec<_>>(), array), (vec![1, 2], [3, 4]));
+
+    let node: Node<'_> = Node::HTTPError(Box::new(|byte| vec![byte; 2]));
+    assert_eq!(unsafe { node.as_http_error_unchecked_dbg() }(7), [7, 7]);
+
+    let mut node: Node<'_> = Node::Empty();
+    unsafe { node.as_empty_mut_unchecked_dbg() };
+
+    let node: Node<'_> = Node::Leaf("leaf");
+    assert_eq!(unsafe { *node.as_leaf_unchecked_dbg() }, "leaf");
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "internal error: entered unreachable code: called `Shape::as_rect_unchecked_dbg()` on a `Shape::Circle` value"]
+fn failure() {
+    let shape = Shape::Circle { r: 7 };
+    unsafe { shape.as_rect_unchecked_dbg() };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "internal error: entered unreachable code: called `Shape::into_point_unchecked_dbg()` on a `Shape::Rect` value"]
+fn into_failure() {
+    let shape = Shape::Rect { w: 2, h: 3 };
+    unsafe { shape.into_point_unchecked_dbg() };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+#[should_panic = "internal error: entered unreachable code: called `Node::as_leaf_mut_unchecked_dbg()` on a `Node::Empty` value"]
+fn generic_failure() {
+    let mut node: Node<'_> = Node::Empty();
+    unsafe { node.as_leaf_mut_unchecked_dbg() };
+}
+
+#[cfg(all(miniunchecked_checks_enabled, not(feature = "abort-on-violation")))]
+#[test]
+fn location() {
+    use std::{panic, sync::mpsc};
+
+    let (sender, receiver) = mpsc::channel();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info.location().unwrap();
+        sender
+            .send((location.file().to_string(), location.line()))
+            .unwrap();
+    }));
+
+    let line = line!() + 1;
+    let result = panic::catch_unwind(|| unsafe { Shape::Point.into_circle_unchecked_dbg() });
+    panic::set_hook(default_hook);
+
+    assert!(result.is_err());
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        [(file!().to_string(), line)]
+    );
+}